@@ -32,6 +32,41 @@ impl BinEntry {
     fn is_number(&self) -> bool {
         self.word_class == "to"
     }
+
+    /// Common vocabulary is classified "alm" in BÍN. Everything else is a
+    /// proper name of some kind: "ism" (Icelandic personal names), "örn"
+    /// (place names), "fyr" (companies), and so on.
+    fn is_common(&self) -> bool {
+        self.classification == "alm"
+    }
+}
+
+/// Which BÍN classifications a lookup should select from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Register {
+    /// Prefer common vocabulary ("alm"), falling back to any paradigm.
+    Common,
+    /// Only proper names (anything but "alm").
+    Proper,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum NameKind {
+    Personal,
+    Place,
+    Company,
+    Other,
+}
+
+impl NameKind {
+    fn from_classification(classification: &str) -> Self {
+        match classification {
+            "ism" | "erm" | "föð" | "móð" | "ætt" | "gæl" | "bibl" => NameKind::Personal,
+            "örn" | "bær" | "göt" | "lönd" | "þor" | "erl" => NameKind::Place,
+            "fyr" => NameKind::Company,
+            _ => NameKind::Other,
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -80,6 +115,12 @@ pub struct NounEntry {
     pub gen_pl_def: Option<String>,
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub struct ProperNounEntry {
+    pub kind: NameKind,
+    pub noun: NounEntry,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct AdjectiveEntry {
     pub masc_nom_sg_strong: Option<String>,
@@ -184,6 +225,177 @@ pub struct IndefinitePronounEntry {
     pub neut_gen_pl: Option<String>,
 }
 
+/// The endings of a set of forms after the stem they share, such as "ur",
+/// "s", and "ar" for "hestur", "hests", and "hestar". An empty ending is
+/// written as "-" alone. The stem may have the u-umlaut of a to ö, as in
+/// "hjarta", "hjörtu".
+fn endings(forms: &[&str]) -> String {
+    let same = |a: char, b: char| a == b || (a == 'a' && b == 'ö') || (a == 'ö' && b == 'a');
+    let stem = forms[1..].iter().fold(forms[0].chars().count(), |stem, form| {
+        forms[0].chars().zip(form.chars()).take(stem).take_while(|&(a, b)| same(a, b)).count()
+    });
+
+    forms
+        .iter()
+        .map(|form| format!("-{}", form.chars().skip(stem).collect::<String>()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Gender {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Gender::Masculine => "masculine",
+            Gender::Feminine => "feminine",
+            Gender::Neuter => "neuter",
+        }
+    }
+}
+
+const VOWELS: &str = "aeiouyáéíóúýæö";
+
+impl NounEntry {
+    /// The declension class of the noun, given as its gender and the endings
+    /// of the nominative singular, genitive singular, and nominative plural
+    /// (if it has one), as in "masculine: -ur, -s, -ar".
+    pub fn declension_class(&self) -> Option<String> {
+        let mut forms = vec![self.nom_sg.as_deref()?, self.gen_sg.as_deref()?];
+        forms.extend(self.nom_pl.as_deref());
+        Some(format!("{}: {}", self.gender.name(), endings(&forms)))
+    }
+
+    /// Whether the noun is weak, with a genitive singular ending in a vowel
+    /// (tími, tíma; kona, konu; auga, auga), rather than strong.
+    pub fn is_weak(&self) -> Option<bool> {
+        let gen_sg = self.gen_sg.as_deref()?;
+        Some(gen_sg.ends_with(|c| VOWELS.contains(c)))
+    }
+
+    /// Whether the stem of the noun changes in the plural, as in maður,
+    /// menn, or bók, bækur. The u-umlaut of a to ö, as in barn, börn, is
+    /// regular, and isn't counted.
+    pub fn is_irregular(&self) -> Option<bool> {
+        let (nom_sg, gen_sg) = (self.nom_sg.as_deref()?, self.gen_sg.as_deref()?);
+        let without_u_umlaut = |form: &str| form.replace('ö', "a");
+
+        // Weak nouns end in a vowel that isn't part of the stem, and which
+        // the genitive may share, as in auga, auga.
+        let nom_sg = nom_sg.strip_suffix(|c| VOWELS.contains(c)).unwrap_or(nom_sg);
+
+        let stem: String = nom_sg
+            .chars()
+            .zip(gen_sg.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a)
+            .collect();
+        let nom_pl = self.nom_pl.as_deref()?;
+
+        Some(!without_u_umlaut(nom_pl).starts_with(&without_u_umlaut(&stem)))
+    }
+}
+
+impl AdjectiveEntry {
+    /// The declension class of the adjective, given as the endings of the
+    /// strong nominative singular in each gender, as in "-ur, -, -t".
+    pub fn declension_class(&self) -> Option<String> {
+        let forms = [
+            self.masc_nom_sg_strong.as_deref()?,
+            self.fem_nom_sg_strong.as_deref()?,
+            self.neut_nom_sg_strong.as_deref()?,
+        ];
+
+        if forms.iter().all(|form| *form == forms[0]) {
+            Some("indeclinable".to_string())
+        } else {
+            Some(endings(&forms))
+        }
+    }
+}
+
+impl VerbEntry {
+    /// The conjugation class of the verb. Weak verbs form their past tense
+    /// with a dental suffix, and are told apart by it, as in "weak (-aði)";
+    /// all others are strong.
+    pub fn conjugation_class(&self) -> Option<String> {
+        let past = self.past_ind_first_sg.as_deref()?;
+
+        let class = match ["aði", "ði", "di", "ti"].iter().find(|suffix| past.ends_with(*suffix))
+        {
+            Some(suffix) => format!("weak (-{})", suffix),
+            None => "strong".to_string(),
+        };
+
+        Some(class)
+    }
+}
+
+/// Verbs whose present tense follows neither the weak nor the strong
+/// pattern: the preterite-present verbs, and vera and vilja.
+const IRREGULAR_VERBS: [&str; 11] =
+    ["eiga", "kunna", "mega", "muna", "munu", "skulu", "unna", "vita", "þurfa", "vera", "vilja"];
+
+/// Whether the verb with this infinitive is irregular.
+pub fn is_irregular_verb(infinitive: &str) -> bool {
+    IRREGULAR_VERBS.contains(&infinitive)
+}
+
+/// Codes of the grammatical features in BÍN tags, with their descriptions.
+/// The active voice and indicative mood go without saying.
+const TAG_CODES: [(&str, &str); 33] = [
+    ("SAGNB", "supine"),
+    ("LHÞT", "past participle"),
+    ("LH-NT", "present participle"),
+    ("NH", "infinitive"),
+    ("BH", "imperative"),
+    ("GM", ""),
+    ("MM", "middle"),
+    ("FH", ""),
+    ("VH", "subjunctive"),
+    ("NT", "present"),
+    ("ÞT", "past"),
+    ("1P", "1st person"),
+    ("2P", "2nd person"),
+    ("3P", "3rd person"),
+    ("OP", "impersonal"),
+    ("SP", "question"),
+    ("FSB", "strong"),
+    ("FVB", "weak"),
+    ("MST", "comparative"),
+    ("ESB", "superlative strong"),
+    ("EVB", "superlative weak"),
+    ("SB", "strong"),
+    ("VB", "weak"),
+    ("KK", "masculine"),
+    ("KVK", "feminine"),
+    ("HK", "neuter"),
+    ("NF", "nominative"),
+    ("ÞF", "accusative"),
+    ("ÞGF", "dative"),
+    ("EF", "genitive"),
+    ("ET", "singular"),
+    ("FT", "plural"),
+    ("gr", "definite"),
+];
+
+/// Describe a BÍN tag in words, as "dative singular definite" for
+/// "ÞGFETgr". Parts of the tag that aren't known are left out.
+pub fn describe_tag(tag: &str) -> String {
+    let mut words: Vec<&str> = Vec::new();
+    let mut rest = tag;
+
+    while let Some(first) = rest.chars().next() {
+        match TAG_CODES.iter().find(|(code, _)| rest.starts_with(code)) {
+            Some((code, description)) => {
+                words.extend(Some(*description).filter(|d| !d.is_empty()));
+                rest = &rest[code.len()..];
+            }
+            None => rest = &rest[first.len_utf8()..],
+        }
+    }
+
+    words.join(" ")
+}
+
 pub struct BinData {
     pub data: BTreeMap<String, Vec<BinEntry>>,
 }
@@ -208,13 +420,93 @@ impl BinData {
             let form = record.get(4).unwrap().to_string();
             let tag = record.get(5).unwrap().to_string();
 
-            let entry = bin_data.data.entry(lemma).or_insert_with(Vec::new);
+            let entry = bin_data.data.entry(lemma).or_default();
             entry.push(BinEntry { id, word_class, classification, form, tag });
         }
 
         Ok(bin_data)
     }
 
+    /// Select the entries of a single paradigm of `root`.
+    ///
+    /// A lemma may have several paradigms in BÍN, each with its own id: a
+    /// common noun and a personal name spelled alike, or two unrelated
+    /// homographs. Only entries accepted by `word_class` are considered, and
+    /// forms are never mixed across paradigms. If `id` is given, that
+    /// paradigm is used, otherwise paradigms in the requested register are
+    /// preferred.
+    fn paradigm(
+        &self,
+        root: &str,
+        id: Option<u64>,
+        word_class: fn(&BinEntry) -> bool,
+        register: Register,
+    ) -> Option<Vec<&BinEntry>> {
+        let entries =
+            self.data.get(root)?.iter().filter(|&e| word_class(e)).collect::<Vec<&BinEntry>>();
+
+        if let Some(id) = id {
+            let entries = entries.into_iter().filter(|e| e.id == id).collect::<Vec<&BinEntry>>();
+            return if entries.is_empty() { None } else { Some(entries) };
+        }
+
+        let preferred = entries.iter().find(|&&e| match register {
+            Register::Common => e.is_common(),
+            Register::Proper => !e.is_common(),
+        });
+
+        let id = match (preferred, register) {
+            (Some(e), _) => e.id,
+            (None, Register::Common) => entries.first()?.id,
+            (None, Register::Proper) => return None,
+        };
+
+        Some(entries.into_iter().filter(|e| e.id == id).collect())
+    }
+
+    /// The paradigms of `root`, as their BÍN id, word class, and whether
+    /// they are common vocabulary.
+    pub fn paradigms(&self, root: &str) -> Vec<(u64, &str, bool)> {
+        let mut paradigms = Vec::new();
+
+        for entry in self.data.get(root).into_iter().flatten() {
+            let paradigm = (entry.id, entry.word_class.as_str(), entry.is_common());
+            if !paradigms.contains(&paradigm) {
+                paradigms.push(paradigm);
+            }
+        }
+
+        paradigms
+    }
+
+    /// The forms of the paradigm of `root` with the given BÍN id, each with
+    /// its tag, as in ("aðalhellinum", "ÞGFETgr").
+    pub fn forms(&self, root: &str, id: u64) -> Vec<(&str, &str)> {
+        self.data
+            .get(root)
+            .into_iter()
+            .flatten()
+            .filter(|e| e.id == id)
+            .map(|e| (e.form.as_str(), e.tag.as_str()))
+            .collect()
+    }
+
+    /// The distinct word classes of the paradigms of `root`, each with
+    /// whether it is common vocabulary. With an `id`, only that paradigm is
+    /// considered.
+    pub fn word_classes(&self, root: &str, id: Option<u64>) -> Vec<(&str, bool)> {
+        let mut word_classes = Vec::new();
+
+        let paradigms = self.paradigms(root).into_iter();
+        for (_, word_class, common) in paradigms.filter(|p| id.is_none_or(|id| p.0 == id)) {
+            if !word_classes.contains(&(word_class, common)) {
+                word_classes.push((word_class, common));
+            }
+        }
+
+        word_classes
+    }
+
     pub fn pronoun(&self, root: &str) -> Option<PronounEntry> {
         // Personal pronouns require some special handling.
         let (entries, tag) = match root {
@@ -260,553 +552,409 @@ impl BinData {
         }
     }
 
-    pub fn number(&self, root: &str) -> Option<NumberEntry> {
-        let entries = self.data.get(root);
-
-        match entries {
-            Some(entries) => {
-                let entries = entries.iter().filter(|&e| e.is_number()).collect::<Vec<&BinEntry>>();
-
-                if entries.is_empty() {
-                    None
-                } else {
-                    Some(NumberEntry {
-                        masc_nom: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-NFET" || e.tag == "KK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_acc: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-ÞFET" || e.tag == "KK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_dat: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-ÞGFET" || e.tag == "KK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_gen: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-EFET" || e.tag == "KK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_nom: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-NFET" || e.tag == "KVK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_acc: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-ÞFET" || e.tag == "KVK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_dat: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-ÞGFET" || e.tag == "KVK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_gen: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-EFET" || e.tag == "KVK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_nom: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-NFET" || e.tag == "HK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_acc: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-ÞFET" || e.tag == "HK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_dat: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-ÞGFET" || e.tag == "HK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_gen: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-EFET" || e.tag == "HK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                    })
-                }
-            }
-            None => None,
-        }
+    pub fn number(&self, root: &str, id: Option<u64>) -> Option<NumberEntry> {
+        let entries = self.paradigm(root, id, BinEntry::is_number, Register::Common)?;
+
+        Some(NumberEntry {
+            masc_nom: entries
+                .iter()
+                .find(|&&e| e.tag == "KK-NFET" || e.tag == "KK-NFFT")
+                .map(|&e| e.form.to_string()),
+            masc_acc: entries
+                .iter()
+                .find(|&&e| e.tag == "KK-ÞFET" || e.tag == "KK-ÞFFT")
+                .map(|&e| e.form.to_string()),
+            masc_dat: entries
+                .iter()
+                .find(|&&e| e.tag == "KK-ÞGFET" || e.tag == "KK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            masc_gen: entries
+                .iter()
+                .find(|&&e| e.tag == "KK-EFET" || e.tag == "KK-EFFT")
+                .map(|&e| e.form.to_string()),
+            fem_nom: entries
+                .iter()
+                .find(|&&e| e.tag == "KVK-NFET" || e.tag == "KVK-NFFT")
+                .map(|&e| e.form.to_string()),
+            fem_acc: entries
+                .iter()
+                .find(|&&e| e.tag == "KVK-ÞFET" || e.tag == "KVK-ÞFFT")
+                .map(|&e| e.form.to_string()),
+            fem_dat: entries
+                .iter()
+                .find(|&&e| e.tag == "KVK-ÞGFET" || e.tag == "KVK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            fem_gen: entries
+                .iter()
+                .find(|&&e| e.tag == "KVK-EFET" || e.tag == "KVK-EFFT")
+                .map(|&e| e.form.to_string()),
+            neut_nom: entries
+                .iter()
+                .find(|&&e| e.tag == "HK-NFET" || e.tag == "HK-NFFT")
+                .map(|&e| e.form.to_string()),
+            neut_acc: entries
+                .iter()
+                .find(|&&e| e.tag == "HK-ÞFET" || e.tag == "HK-ÞFFT")
+                .map(|&e| e.form.to_string()),
+            neut_dat: entries
+                .iter()
+                .find(|&&e| e.tag == "HK-ÞGFET" || e.tag == "HK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            neut_gen: entries
+                .iter()
+                .find(|&&e| e.tag == "HK-EFET" || e.tag == "HK-EFFT")
+                .map(|&e| e.form.to_string()),
+        })
     }
 
-    pub fn indefinite_pronoun(&self, root: &str) -> Option<IndefinitePronounEntry> {
-        let entries = self.data.get(root);
-
-        match entries {
-            Some(entries) => {
-                let entries = entries
-                    .iter()
-                    .filter(|&e| e.is_indefinite_pronoun())
-                    .collect::<Vec<&BinEntry>>();
-
-                if entries.is_empty() {
-                    None
-                } else {
-                    Some(IndefinitePronounEntry {
-                        masc_nom_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-NFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_acc_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_dat_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_gen_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-EFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_nom_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-NFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_acc_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_dat_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_gen_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-EFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_nom_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-NFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_acc_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_dat_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_gen_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-EFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_nom_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_acc_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_dat_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_gen_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_nom_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_acc_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_dat_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_gen_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "KVK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_nom_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_acc_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_dat_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_gen_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "HK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                    })
-                }
-            }
-            None => None,
-        }
+    pub fn indefinite_pronoun(
+        &self,
+        root: &str,
+        id: Option<u64>,
+    ) -> Option<IndefinitePronounEntry> {
+        let entries = self.paradigm(root, id, BinEntry::is_indefinite_pronoun, Register::Common)?;
+
+        Some(IndefinitePronounEntry {
+            masc_nom_sg: entries.iter().find(|&&e| e.tag == "KK-NFET").map(|&e| e.form.to_string()),
+            masc_acc_sg: entries.iter().find(|&&e| e.tag == "KK-ÞFET").map(|&e| e.form.to_string()),
+            masc_dat_sg: entries
+                .iter()
+                .find(|&&e| e.tag == "KK-ÞGFET")
+                .map(|&e| e.form.to_string()),
+            masc_gen_sg: entries.iter().find(|&&e| e.tag == "KK-EFET").map(|&e| e.form.to_string()),
+            fem_nom_sg: entries.iter().find(|&&e| e.tag == "KVK-NFET").map(|&e| e.form.to_string()),
+            fem_acc_sg: entries.iter().find(|&&e| e.tag == "KVK-ÞFET").map(|&e| e.form.to_string()),
+            fem_dat_sg: entries
+                .iter()
+                .find(|&&e| e.tag == "KVK-ÞGFET")
+                .map(|&e| e.form.to_string()),
+            fem_gen_sg: entries.iter().find(|&&e| e.tag == "KVK-EFET").map(|&e| e.form.to_string()),
+            neut_nom_sg: entries.iter().find(|&&e| e.tag == "HK-NFET").map(|&e| e.form.to_string()),
+            neut_acc_sg: entries.iter().find(|&&e| e.tag == "HK-ÞFET").map(|&e| e.form.to_string()),
+            neut_dat_sg: entries
+                .iter()
+                .find(|&&e| e.tag == "HK-ÞGFET")
+                .map(|&e| e.form.to_string()),
+            neut_gen_sg: entries.iter().find(|&&e| e.tag == "HK-EFET").map(|&e| e.form.to_string()),
+            masc_nom_pl: entries.iter().find(|&&e| e.tag == "KK-NFFT").map(|&e| e.form.to_string()),
+            masc_acc_pl: entries.iter().find(|&&e| e.tag == "KK-ÞFFT").map(|&e| e.form.to_string()),
+            masc_dat_pl: entries
+                .iter()
+                .find(|&&e| e.tag == "KK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            masc_gen_pl: entries.iter().find(|&&e| e.tag == "KK-EFFT").map(|&e| e.form.to_string()),
+            fem_nom_pl: entries.iter().find(|&&e| e.tag == "KVK-NFFT").map(|&e| e.form.to_string()),
+            fem_acc_pl: entries.iter().find(|&&e| e.tag == "KVK-ÞFFT").map(|&e| e.form.to_string()),
+            fem_dat_pl: entries
+                .iter()
+                .find(|&&e| e.tag == "KVK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            fem_gen_pl: entries.iter().find(|&&e| e.tag == "KVK-EFFT").map(|&e| e.form.to_string()),
+            neut_nom_pl: entries.iter().find(|&&e| e.tag == "HK-NFFT").map(|&e| e.form.to_string()),
+            neut_acc_pl: entries.iter().find(|&&e| e.tag == "HK-ÞFFT").map(|&e| e.form.to_string()),
+            neut_dat_pl: entries
+                .iter()
+                .find(|&&e| e.tag == "HK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            neut_gen_pl: entries.iter().find(|&&e| e.tag == "HK-EFFT").map(|&e| e.form.to_string()),
+        })
     }
 
-    pub fn adjective(&self, root: &str) -> Option<AdjectiveEntry> {
-        let entries = self.data.get(root);
+    pub fn adjective(&self, root: &str, id: Option<u64>) -> Option<AdjectiveEntry> {
+        let entries = self.paradigm(root, id, BinEntry::is_adjective, Register::Common)?;
+
+        Some(AdjectiveEntry {
+            masc_nom_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KK-NFET")
+                .map(|&e| e.form.to_string()),
+            masc_acc_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KK-ÞFET")
+                .map(|&e| e.form.to_string()),
+            masc_dat_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KK-ÞGFET")
+                .map(|&e| e.form.to_string()),
+            masc_gen_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KK-EFET")
+                .map(|&e| e.form.to_string()),
+            fem_nom_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KVK-NFET")
+                .map(|&e| e.form.to_string()),
+            fem_acc_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KVK-ÞFET")
+                .map(|&e| e.form.to_string()),
+            fem_dat_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KVK-ÞGFET")
+                .map(|&e| e.form.to_string()),
+            fem_gen_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KVK-EFET")
+                .map(|&e| e.form.to_string()),
+            neut_nom_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-HK-NFET")
+                .map(|&e| e.form.to_string()),
+            neut_acc_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-HK-ÞFET")
+                .map(|&e| e.form.to_string()),
+            neut_dat_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-HK-ÞGFET")
+                .map(|&e| e.form.to_string()),
+            neut_gen_sg_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-HK-EFET")
+                .map(|&e| e.form.to_string()),
+            masc_nom_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KK-NFFT")
+                .map(|&e| e.form.to_string()),
+            masc_acc_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KK-ÞFFT")
+                .map(|&e| e.form.to_string()),
+            masc_dat_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            masc_gen_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KK-EFFT")
+                .map(|&e| e.form.to_string()),
+            fem_nom_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KVK-NFFT")
+                .map(|&e| e.form.to_string()),
+            fem_acc_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KVK-ÞFFT")
+                .map(|&e| e.form.to_string()),
+            fem_dat_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KVK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            fem_gen_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-KVK-EFFT")
+                .map(|&e| e.form.to_string()),
+            neut_nom_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-HK-NFFT")
+                .map(|&e| e.form.to_string()),
+            neut_acc_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-HK-ÞFFT")
+                .map(|&e| e.form.to_string()),
+            neut_dat_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-HK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            neut_gen_pl_strong: entries
+                .iter()
+                .find(|&&e| e.tag == "FSB-HK-EFFT")
+                .map(|&e| e.form.to_string()),
+            masc_nom_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KK-NFET")
+                .map(|&e| e.form.to_string()),
+            masc_acc_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KK-ÞFET")
+                .map(|&e| e.form.to_string()),
+            masc_dat_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KK-ÞGFET")
+                .map(|&e| e.form.to_string()),
+            masc_gen_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KK-EFET")
+                .map(|&e| e.form.to_string()),
+            fem_nom_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KVK-NFET")
+                .map(|&e| e.form.to_string()),
+            fem_acc_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KVK-ÞFET")
+                .map(|&e| e.form.to_string()),
+            fem_dat_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KVK-ÞGFET")
+                .map(|&e| e.form.to_string()),
+            fem_gen_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KVK-EFET")
+                .map(|&e| e.form.to_string()),
+            neut_nom_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-HK-NFET")
+                .map(|&e| e.form.to_string()),
+            neut_acc_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-HK-ÞFET")
+                .map(|&e| e.form.to_string()),
+            neut_dat_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-HK-ÞGFET")
+                .map(|&e| e.form.to_string()),
+            neut_gen_sg_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-HK-EFET")
+                .map(|&e| e.form.to_string()),
+            masc_nom_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KK-NFFT")
+                .map(|&e| e.form.to_string()),
+            masc_acc_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KK-ÞFFT")
+                .map(|&e| e.form.to_string()),
+            masc_dat_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            masc_gen_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KK-EFFT")
+                .map(|&e| e.form.to_string()),
+            fem_nom_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KVK-NFFT")
+                .map(|&e| e.form.to_string()),
+            fem_acc_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KVK-ÞFFT")
+                .map(|&e| e.form.to_string()),
+            fem_dat_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KVK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            fem_gen_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-KVK-EFFT")
+                .map(|&e| e.form.to_string()),
+            neut_nom_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-HK-NFFT")
+                .map(|&e| e.form.to_string()),
+            neut_acc_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-HK-ÞFFT")
+                .map(|&e| e.form.to_string()),
+            neut_dat_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-HK-ÞGFFT")
+                .map(|&e| e.form.to_string()),
+            neut_gen_pl_weak: entries
+                .iter()
+                .find(|&&e| e.tag == "FVB-HK-EFFT")
+                .map(|&e| e.form.to_string()),
+        })
+    }
 
-        match entries {
-            Some(entries) => {
-                let entries =
-                    entries.iter().filter(|&e| e.is_adjective()).collect::<Vec<&BinEntry>>();
+    pub fn noun(&self, root: &str, id: Option<u64>) -> Option<NounEntry> {
+        let entries = self.paradigm(root, id, BinEntry::is_noun, Register::Common)?;
 
-                if entries.is_empty() {
-                    None
-                } else {
-                    Some(AdjectiveEntry {
-                        masc_nom_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KK-NFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_acc_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KK-ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_dat_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KK-ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_gen_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KK-EFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_nom_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KVK-NFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_acc_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KVK-ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_dat_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KVK-ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_gen_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KVK-EFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_nom_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-HK-NFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_acc_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-HK-ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_dat_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-HK-ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_gen_sg_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-HK-EFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_nom_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_acc_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_dat_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_gen_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_nom_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KVK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_acc_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KVK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_dat_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KVK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_gen_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-KVK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_nom_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-HK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_acc_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-HK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_dat_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-HK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_gen_pl_strong: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FSB-HK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_nom_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KK-NFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_acc_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KK-ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_dat_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KK-ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_gen_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KK-EFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_nom_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KVK-NFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_acc_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KVK-ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_dat_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KVK-ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        fem_gen_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KVK-EFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_nom_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-HK-NFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_acc_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-HK-ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_dat_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-HK-ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        neut_gen_sg_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-HK-EFET")
-                            .map(|&e| e.form.to_string()),
-                        masc_nom_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_acc_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_dat_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        masc_gen_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_nom_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KVK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_acc_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KVK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_dat_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KVK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        fem_gen_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-KVK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_nom_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-HK-NFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_acc_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-HK-ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_dat_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-HK-ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        neut_gen_pl_weak: entries
-                            .iter()
-                            .find(|&&e| e.tag == "FVB-HK-EFFT")
-                            .map(|&e| e.form.to_string()),
-                    })
-                }
-            }
-            None => None,
-        }
+        Some(noun_entry(&entries))
     }
 
-    pub fn noun(&self, root: &str) -> Option<NounEntry> {
-        let entries = self.data.get(root);
-
-        match entries {
-            Some(entries) => {
-                let entries = entries.iter().filter(|&e| e.is_noun()).collect::<Vec<&BinEntry>>();
+    pub fn proper_noun(&self, root: &str, id: Option<u64>) -> Option<ProperNounEntry> {
+        let entries = self.paradigm(root, id, BinEntry::is_noun, Register::Proper)?;
 
-                if entries.is_empty() {
-                    None
-                } else {
-                    let gender = entries.first().unwrap().word_class.as_str();
-                    Some(NounEntry {
-                        gender: match gender {
-                            "kvk" => Gender::Feminine,
-                            "hk" => Gender::Neuter,
-                            _ => Gender::Masculine,
-                        },
-                        nom_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "NFET")
-                            .map(|&e| e.form.to_string()),
-                        acc_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "ÞFET")
-                            .map(|&e| e.form.to_string()),
-                        dat_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "ÞGFET")
-                            .map(|&e| e.form.to_string()),
-                        gen_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "EFET")
-                            .map(|&e| e.form.to_string()),
-                        nom_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "NFFT")
-                            .map(|&e| e.form.to_string()),
-                        acc_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "ÞFFT")
-                            .map(|&e| e.form.to_string()),
-                        dat_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "ÞGFFT")
-                            .map(|&e| e.form.to_string()),
-                        gen_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "EFFT")
-                            .map(|&e| e.form.to_string()),
-                        nom_sg_def: entries
-                            .iter()
-                            .find(|&&e| e.tag == "NFETgr")
-                            .map(|&e| e.form.to_string()),
-                        acc_sg_def: entries
-                            .iter()
-                            .find(|&&e| e.tag == "ÞFETgr")
-                            .map(|&e| e.form.to_string()),
-                        dat_sg_def: entries
-                            .iter()
-                            .find(|&&e| e.tag == "ÞGFETgr")
-                            .map(|&e| e.form.to_string()),
-                        gen_sg_def: entries
-                            .iter()
-                            .find(|&&e| e.tag == "EFETgr")
-                            .map(|&e| e.form.to_string()),
-                        nom_pl_def: entries
-                            .iter()
-                            .find(|&&e| e.tag == "NFFTgr")
-                            .map(|&e| e.form.to_string()),
-                        acc_pl_def: entries
-                            .iter()
-                            .find(|&&e| e.tag == "ÞFFTgr")
-                            .map(|&e| e.form.to_string()),
-                        dat_pl_def: entries
-                            .iter()
-                            .find(|&&e| e.tag == "ÞGFFTgr")
-                            .map(|&e| e.form.to_string()),
-                        gen_pl_def: entries
-                            .iter()
-                            .find(|&&e| e.tag == "EFFTgr")
-                            .map(|&e| e.form.to_string()),
-                    })
-                }
-            }
-            None => None,
-        }
+        Some(ProperNounEntry {
+            kind: NameKind::from_classification(&entries.first().unwrap().classification),
+            noun: noun_entry(&entries),
+        })
     }
 
-    pub fn verb(&self, root: &str) -> Option<VerbEntry> {
-        let entries = self.data.get(root);
-
-        match entries {
-            Some(entries) => {
-                let entries = entries.iter().filter(|&e| e.is_verb()).collect::<Vec<&BinEntry>>();
+    pub fn verb(&self, root: &str, id: Option<u64>) -> Option<VerbEntry> {
+        let entries = self.paradigm(root, id, BinEntry::is_verb, Register::Common)?;
+
+        Some(VerbEntry {
+            pres_ind_first_sg: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-NT-1P-ET")
+                .map(|&e| e.form.to_string()),
+            pres_ind_second_sg: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-NT-2P-ET")
+                .map(|&e| e.form.to_string()),
+            pres_ind_third_sg: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-NT-3P-ET")
+                .map(|&e| e.form.to_string()),
+            pres_ind_first_pl: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-NT-1P-FT")
+                .map(|&e| e.form.to_string()),
+            pres_ind_second_pl: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-NT-2P-FT")
+                .map(|&e| e.form.to_string()),
+            pres_ind_third_pl: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-NT-3P-FT")
+                .map(|&e| e.form.to_string()),
+            // Past Indicative
+            past_ind_first_sg: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-ÞT-1P-ET")
+                .map(|&e| e.form.to_string()),
+            past_ind_second_sg: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-ÞT-2P-ET")
+                .map(|&e| e.form.to_string()),
+            past_ind_third_sg: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-ÞT-3P-ET")
+                .map(|&e| e.form.to_string()),
+            past_ind_first_pl: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-ÞT-1P-FT")
+                .map(|&e| e.form.to_string()),
+            past_ind_second_pl: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-ÞT-2P-FT")
+                .map(|&e| e.form.to_string()),
+            past_ind_third_pl: entries
+                .iter()
+                .find(|&&e| e.tag == "GM-FH-ÞT-3P-FT")
+                .map(|&e| e.form.to_string()),
+        })
+    }
+}
 
-                if entries.is_empty() {
-                    None
-                } else {
-                    Some(VerbEntry {
-                        pres_ind_first_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-NT-1P-ET")
-                            .map(|&e| e.form.to_string()),
-                        pres_ind_second_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-NT-2P-ET")
-                            .map(|&e| e.form.to_string()),
-                        pres_ind_third_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-NT-3P-ET")
-                            .map(|&e| e.form.to_string()),
-                        pres_ind_first_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-NT-1P-FT")
-                            .map(|&e| e.form.to_string()),
-                        pres_ind_second_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-NT-2P-FT")
-                            .map(|&e| e.form.to_string()),
-                        pres_ind_third_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-NT-3P-FT")
-                            .map(|&e| e.form.to_string()),
-                        // Past Indicative
-                        past_ind_first_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-ÞT-1P-ET")
-                            .map(|&e| e.form.to_string()),
-                        past_ind_second_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-ÞT-2P-ET")
-                            .map(|&e| e.form.to_string()),
-                        past_ind_third_sg: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-ÞT-3P-ET")
-                            .map(|&e| e.form.to_string()),
-                        past_ind_first_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-ÞT-1P-FT")
-                            .map(|&e| e.form.to_string()),
-                        past_ind_second_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-ÞT-2P-FT")
-                            .map(|&e| e.form.to_string()),
-                        past_ind_third_pl: entries
-                            .iter()
-                            .find(|&&e| e.tag == "GM-FH-ÞT-3P-FT")
-                            .map(|&e| e.form.to_string()),
-                    })
-                }
-            }
-            None => None,
-        }
+fn noun_entry(entries: &[&BinEntry]) -> NounEntry {
+    let gender = entries.first().unwrap().word_class.as_str();
+    NounEntry {
+        gender: match gender {
+            "kvk" => Gender::Feminine,
+            "hk" => Gender::Neuter,
+            _ => Gender::Masculine,
+        },
+        nom_sg: entries.iter().find(|&&e| e.tag == "NFET").map(|&e| e.form.to_string()),
+        acc_sg: entries.iter().find(|&&e| e.tag == "ÞFET").map(|&e| e.form.to_string()),
+        dat_sg: entries.iter().find(|&&e| e.tag == "ÞGFET").map(|&e| e.form.to_string()),
+        gen_sg: entries.iter().find(|&&e| e.tag == "EFET").map(|&e| e.form.to_string()),
+        nom_pl: entries.iter().find(|&&e| e.tag == "NFFT").map(|&e| e.form.to_string()),
+        acc_pl: entries.iter().find(|&&e| e.tag == "ÞFFT").map(|&e| e.form.to_string()),
+        dat_pl: entries.iter().find(|&&e| e.tag == "ÞGFFT").map(|&e| e.form.to_string()),
+        gen_pl: entries.iter().find(|&&e| e.tag == "EFFT").map(|&e| e.form.to_string()),
+        nom_sg_def: entries.iter().find(|&&e| e.tag == "NFETgr").map(|&e| e.form.to_string()),
+        acc_sg_def: entries.iter().find(|&&e| e.tag == "ÞFETgr").map(|&e| e.form.to_string()),
+        dat_sg_def: entries.iter().find(|&&e| e.tag == "ÞGFETgr").map(|&e| e.form.to_string()),
+        gen_sg_def: entries.iter().find(|&&e| e.tag == "EFETgr").map(|&e| e.form.to_string()),
+        nom_pl_def: entries.iter().find(|&&e| e.tag == "NFFTgr").map(|&e| e.form.to_string()),
+        acc_pl_def: entries.iter().find(|&&e| e.tag == "ÞFFTgr").map(|&e| e.form.to_string()),
+        dat_pl_def: entries.iter().find(|&&e| e.tag == "ÞGFFTgr").map(|&e| e.form.to_string()),
+        gen_pl_def: entries.iter().find(|&&e| e.tag == "EFFTgr").map(|&e| e.form.to_string()),
     }
 }
 
@@ -848,6 +996,35 @@ aðalhenda;153961;kvk;alm;aðalhendna;EFFT
 aðalhenda;153961;kvk;alm;aðalhenda;EFFT2
 aðalhenda;153961;kvk;alm;aðalhendnanna;EFFTgr
 aðalhenda;153961;kvk;alm;aðalhendanna;EFFTgr2
+Hlíð;386021;kvk;örn;Hlíð;NFET
+Hlíð;386021;kvk;örn;Hlíð;ÞFET
+Hlíð;386021;kvk;örn;Hlíð;ÞGFET
+Hlíð;386021;kvk;örn;Hlíðar;EFET
+Hlíð;900001;kvk;alm;Hlíð;NFET
+Hlíð;900001;kvk;alm;Hlíðina;ÞFETgr
+Hlíð;900001;kvk;alm;Hlíð;ÞFET
+Hlíð;900001;kvk;alm;Hlíð;ÞGFET
+Hlíð;900001;kvk;alm;Hlíðar;EFET
+Hlíð;900001;kvk;alm;Hlíðar;NFFT
+Hlíð;900001;kvk;alm;Hlíðar;ÞFFT
+Hlíð;900001;kvk;alm;Hlíðum;ÞGFFT
+Hlíð;900001;kvk;alm;Hlíða;EFFT
+auga;900002;hk;alm;auga;NFET
+auga;900002;hk;alm;auga;ÞFET
+auga;900002;hk;alm;auga;ÞGFET
+auga;900002;hk;alm;auga;EFET
+auga;900002;hk;alm;augu;NFFT
+auga;900002;hk;alm;augu;ÞFFT
+auga;900002;hk;alm;augum;ÞGFFT
+auga;900002;hk;alm;augna;EFFT
+hjarta;900003;hk;alm;hjarta;NFET
+hjarta;900003;hk;alm;hjarta;ÞFET
+hjarta;900003;hk;alm;hjarta;ÞGFET
+hjarta;900003;hk;alm;hjarta;EFET
+hjarta;900003;hk;alm;hjörtu;NFFT
+hjarta;900003;hk;alm;hjörtu;ÞFFT
+hjarta;900003;hk;alm;hjörtum;ÞGFFT
+hjarta;900003;hk;alm;hjartna;EFFT
 fallegur;168136;lo;alm;fallegur;FSB-KK-NFET
 fallegur;168136;lo;alm;fallegan;FSB-KK-ÞFET
 fallegur;168136;lo;alm;fallegum;FSB-KK-ÞGFET
@@ -1140,7 +1317,7 @@ hún;403785;pfn;alm;þeirra;EFFT
     #[test]
     pub fn gets_noun_entry() {
         let bin_data = BinData::load(TEST_DATA.as_bytes()).unwrap();
-        let noun_entry = bin_data.noun("aðalhenda").unwrap();
+        let noun_entry = bin_data.noun("aðalhenda", None).unwrap();
 
         assert_eq!(Gender::Feminine, noun_entry.gender);
         // Singular
@@ -1163,10 +1340,42 @@ hún;403785;pfn;alm;þeirra;EFFT
         assert_eq!("aðalhendnanna", noun_entry.gen_pl_def.unwrap());
     }
 
+    #[test]
+    pub fn prefers_common_paradigm() {
+        let bin_data = BinData::load(TEST_DATA.as_bytes()).unwrap();
+
+        // The place name is listed first, but common nouns prefer "alm".
+        let noun_entry = bin_data.noun("Hlíð", None).unwrap();
+        assert_eq!("Hlíð", noun_entry.nom_sg.unwrap());
+        assert_eq!("Hlíðar", noun_entry.nom_pl.unwrap());
+    }
+
+    #[test]
+    pub fn gets_proper_noun_entry() {
+        let bin_data = BinData::load(TEST_DATA.as_bytes()).unwrap();
+        let entry = bin_data.proper_noun("Hlíð", None).unwrap();
+
+        assert_eq!(NameKind::Place, entry.kind);
+        assert_eq!(Gender::Feminine, entry.noun.gender);
+        assert_eq!("Hlíð", entry.noun.nom_sg.unwrap());
+        assert_eq!("Hlíðar", entry.noun.gen_sg.unwrap());
+        // Forms from the common paradigm must not leak in.
+        assert!(entry.noun.nom_pl.is_none());
+        assert!(entry.noun.acc_sg_def.is_none());
+
+        // A paradigm may be chosen by its BÍN id.
+        let entry = bin_data.noun("Hlíð", Some(386021)).unwrap();
+        assert!(entry.nom_pl.is_none());
+        assert!(bin_data.noun("Hlíð", Some(74631)).is_none());
+
+        // There are no proper names among the common nouns.
+        assert!(bin_data.proper_noun("aðalhenda", None).is_none());
+    }
+
     #[test]
     pub fn gets_adjective_entry() {
         let bin_data = BinData::load(TEST_DATA.as_bytes()).unwrap();
-        let adjective_entry = bin_data.adjective("fallegur").unwrap();
+        let adjective_entry = bin_data.adjective("fallegur", None).unwrap();
 
         assert_eq!("fallegur", adjective_entry.masc_nom_sg_strong.unwrap());
         assert_eq!("fallegan", adjective_entry.masc_acc_sg_strong.unwrap());
@@ -1232,7 +1441,7 @@ hún;403785;pfn;alm;þeirra;EFFT
     #[test]
     pub fn gets_verb_entry() {
         let bin_data = BinData::load(TEST_DATA.as_bytes()).unwrap();
-        let verb_entry = bin_data.verb("læra").unwrap();
+        let verb_entry = bin_data.verb("læra", None).unwrap();
 
         assert_eq!("læri", verb_entry.pres_ind_first_sg.unwrap());
         assert_eq!("lærir", verb_entry.pres_ind_second_sg.unwrap());
@@ -1251,6 +1460,75 @@ hún;403785;pfn;alm;þeirra;EFFT
         assert_eq!("lærðu", verb_entry.past_ind_third_pl.unwrap());
     }
 
+    #[test]
+    pub fn lists_word_classes() {
+        let bin_data = BinData::load(TEST_DATA.as_bytes()).unwrap();
+
+        let word_classes = bin_data.word_classes("Hlíð", None);
+        assert_eq!(2, word_classes.len());
+        assert!(word_classes.contains(&("kvk", true)));
+        assert!(word_classes.contains(&("kvk", false)));
+
+        assert_eq!(vec![("kvk", false)], bin_data.word_classes("Hlíð", Some(386021)));
+        assert_eq!(vec![("so", true)], bin_data.word_classes("læra", None));
+        assert!(bin_data.word_classes("hestur", None).is_empty());
+    }
+
+    #[test]
+    pub fn lists_forms() {
+        let bin_data = BinData::load(TEST_DATA.as_bytes()).unwrap();
+
+        let forms = bin_data.forms("aðalhellir", 74631);
+        assert_eq!(16, forms.len());
+        assert!(forms.contains(&("aðalhellinum", "ÞGFETgr")));
+        assert!(bin_data.forms("aðalhellir", 1).is_empty());
+        assert!(bin_data.forms("hestur", 74631).is_empty());
+    }
+
+    #[test]
+    pub fn describes_tags() {
+        assert_eq!("dative singular definite", describe_tag("ÞGFETgr"));
+        assert_eq!("genitive plural", describe_tag("EFFT2"));
+        assert_eq!("strong feminine dative singular", describe_tag("FSB-KVK-ÞGFET"));
+        assert_eq!("past 3rd person plural", describe_tag("GM-FH-ÞT-3P-FT"));
+        assert_eq!(
+            "middle subjunctive present 1st person singular",
+            describe_tag("MM-VH-NT-1P-ET")
+        );
+        assert_eq!(
+            "past participle weak neuter nominative plural",
+            describe_tag("LHÞT-VB-HK-NFFT")
+        );
+        assert_eq!("supine", describe_tag("GM-SAGNB"));
+    }
+
+    #[test]
+    pub fn describes_inflection_classes() {
+        let bin_data = BinData::load(TEST_DATA.as_bytes()).unwrap();
+
+        let noun_entry = bin_data.noun("aðalhenda", None).unwrap();
+        assert_eq!(Some("feminine: -a, -u, -ur".to_string()), noun_entry.declension_class());
+        assert_eq!(Some(true), noun_entry.is_weak());
+        assert_eq!(Some(false), noun_entry.is_irregular());
+
+        for weak_neuter in &["auga", "hjarta"] {
+            let noun_entry = bin_data.noun(weak_neuter, None).unwrap();
+            assert_eq!(Some("neuter: -a, -a, -u".to_string()), noun_entry.declension_class());
+            assert_eq!(Some(true), noun_entry.is_weak());
+            assert_eq!(Some(false), noun_entry.is_irregular());
+        }
+
+        let adjective_entry = bin_data.adjective("fallegur", None).unwrap();
+        assert_eq!(Some("-ur, -, -t".to_string()), adjective_entry.declension_class());
+
+        let verb_entry = bin_data.verb("læra", None).unwrap();
+        assert_eq!(Some("weak (-ði)".to_string()), verb_entry.conjugation_class());
+        assert!(!is_irregular_verb("læra"));
+        assert!(is_irregular_verb("vita"));
+
+        assert_eq!("-, -s, -", endings(&["borð", "borðs", "borð"]));
+    }
+
     #[test]
     pub fn gets_pronoun_entries() {
         let bin_data = BinData::load(TEST_DATA.as_bytes()).unwrap();
@@ -0,0 +1,164 @@
+//! Checks of a wordlist against BÍN, for the `check` subcommand.
+
+use crate::bindata::BinData;
+use crate::dictionary::{Category, Diagnostic, Dictionary, DictionaryKey};
+
+/// Check the words of a dictionary. Returns the problems found while loading
+/// it, along with words that have no definition or are listed twice, and
+/// words that BÍN can't decline or conjugate as listed, sorted by where they
+/// were found.
+pub fn check(dictionary: &Dictionary, bin_data: &BinData) -> Vec<Diagnostic> {
+    let mut diagnostics = dictionary.diagnostics.clone();
+
+    for (key, senses) in &dictionary.entries {
+        for (i, sense) in senses.iter().enumerate() {
+            let mut report =
+                |message| diagnostics.push(Diagnostic { origin: sense.origin.clone(), message });
+
+            if sense.definition.is_empty() {
+                report(format!("{} has no definition", key.root));
+            } else if let Some(first) =
+                senses[..i].iter().find(|other| other.definition == sense.definition)
+            {
+                report(format!(
+                    "{} is listed as a {} twice with the same definition, first at {}",
+                    key.root,
+                    key.category.name(),
+                    first.origin
+                ));
+            }
+        }
+
+        if let Some(message) = check_bin(key, bin_data) {
+            diagnostics.push(Diagnostic { origin: senses[0].origin.clone(), message });
+        }
+    }
+
+    diagnostics.sort_by(|a, b| a.origin.cmp(&b.origin));
+    diagnostics
+}
+
+/// Check that BÍN has a single paradigm for a word that matches its
+/// category, or the paradigm chosen by its BÍN id.
+fn check_bin(key: &DictionaryKey, bin_data: &BinData) -> Option<String> {
+    let root = &key.root;
+
+    match key.category {
+        // These don't inflect, so BÍN isn't needed.
+        Category::Adverb | Category::Phrase | Category::Preposition => return None,
+        // Personal pronouns are looked up by hand, including the plural
+        // pronouns that BÍN lists under the singular.
+        Category::Pronoun => {
+            return match bin_data.pronoun(root) {
+                Some(_) => None,
+                None => Some(format!("{} is not a personal pronoun", root)),
+            }
+        }
+        _ => {}
+    }
+
+    let paradigms: Vec<(u64, &str, bool)> = bin_data
+        .paradigms(root)
+        .into_iter()
+        .filter(|&(id, _, _)| key.bin_id.is_none_or(|bin_id| bin_id == id))
+        .collect();
+
+    if paradigms.is_empty() {
+        return Some(match key.bin_id {
+            Some(bin_id) => format!("BÍN has no paradigm {} for {}", bin_id, root),
+            None => format!("{} is not in BÍN", root),
+        });
+    }
+
+    let matching: Vec<&(u64, &str, bool)> = paradigms
+        .iter()
+        .filter(|&&(_, word_class, common)| {
+            Category::from_word_classes(&[(word_class, common)]).contains(&key.category)
+        })
+        .collect();
+
+    if matching.is_empty() {
+        let mut word_classes: Vec<&str> = Vec::new();
+        for &(_, word_class, _) in &paradigms {
+            if !word_classes.contains(&word_class) {
+                word_classes.push(word_class);
+            }
+        }
+        return Some(format!(
+            "{} is listed as a {}, but its BÍN word classes are: {}",
+            root,
+            key.category.name(),
+            word_classes.join(", ")
+        ));
+    }
+
+    // Lookups prefer common vocabulary, so names only count as homographs
+    // of each other.
+    let common: Vec<&&(u64, &str, bool)> = matching.iter().filter(|p| p.2).collect();
+    let candidates = if common.is_empty() { matching.len() } else { common.len() };
+
+    if key.bin_id.is_none() && candidates > 1 {
+        let ids: Vec<String> = matching.iter().map(|&&(id, _, _)| id.to_string()).collect();
+        return Some(format!(
+            "{} has {} paradigms as a {} in BÍN (ids {}); give a bin_id to choose one",
+            root,
+            candidates,
+            key.category.name(),
+            ids.join(", ")
+        ));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::ColumnMapping;
+
+    const BIN_DATA: &str = "læra;466956;so;alm;læra;GM-NH
+hestur;5634;kk;alm;hestur;NFET
+hestur;5634;kk;alm;hests;EFET
+Hlíð;386021;kvk;örn;Hlíð;NFET
+hlíð;900001;kvk;alm;hlíð;NFET
+bak;1212;hk;alm;bak;NFET
+bak;1213;hk;alm;bak;NFET";
+
+    #[test]
+    pub fn reports_problems() {
+        let bin_data = BinData::load(BIN_DATA.as_bytes()).unwrap();
+
+        let wordlist = "læra\tverb\tto learn
+hestur\tnoun
+hestur\tnoun\thorse
+hestur\tnoun\thorse
+hestur\tverb\tto horse
+Hlíð\tproper_noun\tHlíð
+bak\tnoun\tback
+bak\tnoun\tback\t\t\t\t\t\t\t\t\t1213
+köttur\tnoun\tcat
+ ég \tpronoun\tI
+á\tparticle\ton"
+            .as_bytes();
+
+        let dictionary = Dictionary::load(wordlist, &ColumnMapping::default()).unwrap();
+        let diagnostics: Vec<String> = check(&dictionary, &bin_data)
+            .iter()
+            .map(|d| format!("{}:{}", d.origin.line.unwrap(), d.message))
+            .collect();
+
+        assert_eq!(
+            vec![
+                "2:hestur has no definition",
+                "4:hestur is listed as a noun twice with the same definition, first at :3",
+                "5:hestur is listed as a verb, but its BÍN word classes are: kk",
+                "7:bak has 2 paradigms as a noun in BÍN (ids 1212, 1213); give a bin_id to choose one",
+                "9:köttur is not in BÍN",
+                "10:the root \" ég \" has spaces around it",
+                "10:ég is not a personal pronoun",
+                "11:unknown category \"particle\" for á",
+            ],
+            diagnostics
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! Example sentences from a corpus, such as an Icelandic–English parallel
+//! corpus, found by any inflected form of a word.
+
+use crate::ProgramError;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+/// Sentences longer than this, in words, are too long to be examples.
+const MAX_WORDS: usize = 15;
+
+/// A sentence of the corpus, with its translation if there is one.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Sentence {
+    pub text: String,
+    pub translation: Option<String>,
+}
+
+/// A sentence found for a word, and where the word's form is in it.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Found<'a> {
+    pub sentence: &'a Sentence,
+    /// The byte range of the form in the sentence's text.
+    pub start: usize,
+    pub end: usize,
+    /// The BÍN tags of the form.
+    pub tags: Vec<&'a str>,
+}
+
+/// The sentences of a corpus, indexed by the words in them.
+#[derive(Debug, Default)]
+pub struct Corpus {
+    sentences: Vec<Sentence>,
+    /// The places each word is found at, in lower case, as the index of the
+    /// sentence and the byte range of the word.
+    words: HashMap<String, Vec<(usize, usize, usize)>>,
+}
+
+impl Corpus {
+    /// Read a corpus. Each line holds an Icelandic sentence, optionally
+    /// followed by its translation, separated by a tab. Empty lines and
+    /// lines starting with `#` are skipped, as are sentences too long to be
+    /// examples.
+    pub fn load<T>(reader: T) -> Result<Self, ProgramError>
+    where
+        T: Read,
+    {
+        let mut corpus = Corpus::default();
+
+        for line in BufReader::new(reader).lines() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut columns = line.splitn(2, '\t').map(str::trim);
+            let text = columns.next().unwrap_or_default().to_string();
+            let translation = columns.next().filter(|t| !t.is_empty()).map(str::to_string);
+
+            let words = words(&text);
+            if words.len() > MAX_WORDS {
+                continue;
+            }
+
+            let index = corpus.sentences.len();
+            for (start, end) in words {
+                let word = text[start..end].to_lowercase();
+                corpus.words.entry(word).or_default().push((index, start, end));
+            }
+            corpus.sentences.push(Sentence { text, translation });
+        }
+
+        Ok(corpus)
+    }
+
+    /// Up to `count` of the shortest sentences that contain one of the
+    /// `forms` of a word, each given with its BÍN tag, as in ("hestinum",
+    /// "ÞGFETgr"). Sentences of the same length are taken in corpus order,
+    /// and the first form found in each is the one given.
+    pub fn find<'a>(&'a self, forms: &[(&str, &'a str)], count: usize) -> Vec<Found<'a>> {
+        // A form may have several tags, as "hesti" is both accusative and
+        // dative.
+        let mut tags: Vec<(String, Vec<&'a str>)> = Vec::new();
+        for &(form, tag) in forms {
+            let form = form.to_lowercase();
+            match tags.iter_mut().find(|(f, _)| *f == form) {
+                Some((_, form_tags)) if form_tags.contains(&tag) => {}
+                Some((_, form_tags)) => form_tags.push(tag),
+                None => tags.push((form, vec![tag])),
+            }
+        }
+
+        let mut places: Vec<(usize, usize, usize, &Vec<&'a str>)> = tags
+            .iter()
+            .flat_map(|(form, form_tags)| {
+                let places = self.words.get(form).into_iter().flatten();
+                places.map(move |&(index, start, end)| (index, start, end, form_tags))
+            })
+            .collect();
+        places.sort_by_key(|&(index, start, _, _)| {
+            (self.sentences[index].text.chars().count(), index, start)
+        });
+        places.dedup_by_key(|place| place.0);
+
+        places
+            .into_iter()
+            .take(count)
+            .map(|(index, start, end, tags)| Found {
+                sentence: &self.sentences[index],
+                start,
+                end,
+                tags: tags.clone(),
+            })
+            .collect()
+    }
+}
+
+/// The byte ranges of the words of a text.
+fn words(text: &str) -> Vec<(usize, usize)> {
+    let mut words = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push((s, text.len()));
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORPUS: &str = "# Sentences\n\
+                          Ég gaf hestinum hey.\tI gave the horse hay.\n\
+                          Hestar eru stórir og hestarnir hér eru mjög stórir og fallegir en þeir \
+                          eru líka svangir og þreyttir.\n\
+                          Hesturinn er stór.\tThe horse is big.\n\
+                          \n\
+                          Þetta er hesturinn minn\n";
+
+    #[test]
+    pub fn finds_sentences_by_form() {
+        let corpus = Corpus::load(CORPUS.as_bytes()).unwrap();
+        let forms = [
+            ("hestur", "NFET"),
+            ("hesturinn", "NFETgr"),
+            ("hestinum", "ÞGFETgr"),
+            ("hestar", "NFFT"),
+        ];
+
+        let found = corpus.find(&forms, 2);
+        assert_eq!(2, found.len());
+        assert_eq!("Hesturinn er stór.", found[0].sentence.text);
+        assert_eq!(Some("The horse is big."), found[0].sentence.translation.as_deref());
+        assert_eq!((0, 9), (found[0].start, found[0].end));
+        assert_eq!(vec!["NFETgr"], found[0].tags);
+        assert_eq!("Ég gaf hestinum hey.", found[1].sentence.text);
+        assert_eq!("hestinum", &found[1].sentence.text[found[1].start..found[1].end]);
+
+        let found = corpus.find(&forms, 5);
+        assert_eq!(3, found.len());
+        assert_eq!(None, found[2].sentence.translation);
+
+        assert!(corpus.find(&[("kona", "NFET")], 2).is_empty());
+    }
+
+    #[test]
+    pub fn finds_words() {
+        assert_eq!(vec![(0, 3), (4, 12), (14, 17)], words("Ég hestinum, hey."));
+        assert!(words(" - ").is_empty());
+    }
+}
@@ -1,11 +1,17 @@
 use crate::ProgramError;
-use csv::ReaderBuilder;
-use std::collections::BTreeMap;
+use csv::{ReaderBuilder, StringRecord};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub enum Category {
     Noun,
+    ProperNoun,
     Adjective,
     Verb,
     Adverb,
@@ -13,6 +19,7 @@ pub enum Category {
     Pronoun,
     IndefinitePronoun,
     Number,
+    Preposition,
 }
 
 impl FromStr for Category {
@@ -21,6 +28,7 @@ impl FromStr for Category {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "noun" | "nouns" => Ok(Category::Noun),
+            "proper_noun" | "proper_nouns" => Ok(Category::ProperNoun),
             "adjective" | "adjectives" => Ok(Category::Adjective),
             "verb" | "verbs" => Ok(Category::Verb),
             "adverb" | "adverbs" => Ok(Category::Adverb),
@@ -28,52 +36,1011 @@ impl FromStr for Category {
             "pronoun" | "pronouns" => Ok(Category::Pronoun),
             "indefinite_pronoun" | "indefinite_pronouns" => Ok(Category::IndefinitePronoun),
             "number" | "numbers" => Ok(Category::Number),
+            "preposition" | "prepositions" => Ok(Category::Preposition),
             _ => Err(()),
         }
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+impl Category {
+    pub const ALL: [Category; 10] = [
+        Category::Noun,
+        Category::ProperNoun,
+        Category::Adjective,
+        Category::Verb,
+        Category::Adverb,
+        Category::Phrase,
+        Category::Pronoun,
+        Category::IndefinitePronoun,
+        Category::Number,
+        Category::Preposition,
+    ];
+
+    /// The name of the category, as written in wordlists.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Noun => "noun",
+            Category::ProperNoun => "proper_noun",
+            Category::Adjective => "adjective",
+            Category::Verb => "verb",
+            Category::Adverb => "adverb",
+            Category::Phrase => "phrase",
+            Category::Pronoun => "pronoun",
+            Category::IndefinitePronoun => "indefinite_pronoun",
+            Category::Number => "number",
+            Category::Preposition => "preposition",
+        }
+    }
+
+    /// The categories of a word, from the BÍN word classes of its paradigms
+    /// and whether each is common vocabulary. Common vocabulary is preferred,
+    /// so a word that is also a name is taken to be the common word. Word
+    /// classes without a category of their own, such as conjunctions, are
+    /// left out.
+    pub fn from_word_classes(word_classes: &[(&str, bool)]) -> Vec<Category> {
+        let prefer_common = word_classes.iter().any(|&(_, common)| common);
+
+        let mut categories = Vec::new();
+        for &(word_class, common) in word_classes {
+            let category = match (word_class, common) {
+                (_, false) if prefer_common => None,
+                ("kk" | "kvk" | "hk", true) => Some(Category::Noun),
+                ("kk" | "kvk" | "hk", false) => Some(Category::ProperNoun),
+                ("lo", _) => Some(Category::Adjective),
+                ("so", _) => Some(Category::Verb),
+                ("ao", _) => Some(Category::Adverb),
+                ("fs", _) => Some(Category::Preposition),
+                ("pfn", _) => Some(Category::Pronoun),
+                ("fn", _) => Some(Category::IndefinitePronoun),
+                ("to", _) => Some(Category::Number),
+                _ => None,
+            };
+
+            if let Some(category) = category.filter(|c| !categories.contains(c)) {
+                categories.push(category);
+            }
+        }
+
+        categories
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub struct DictionaryKey {
     pub root: String,
     pub category: Category,
+    /// The BÍN paradigm to use, where the root alone is ambiguous.
+    pub bin_id: Option<u64>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// A grammatical case, as governed by a preposition or verb.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Case {
+    Nominative,
+    Accusative,
+    Dative,
+    Genitive,
+}
+
+impl Case {
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Case::Nominative => "nom.",
+            Case::Accusative => "acc.",
+            Case::Dative => "dat.",
+            Case::Genitive => "gen.",
+        }
+    }
+}
+
+impl FromStr for Case {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().trim_end_matches('.').to_lowercase().as_str() {
+            "nominative" | "nom" | "nf" => Ok(Case::Nominative),
+            "accusative" | "acc" | "þf" => Ok(Case::Accusative),
+            "dative" | "dat" | "þgf" => Ok(Case::Dative),
+            "genitive" | "gen" | "ef" => Ok(Case::Genitive),
+            _ => Err(format!("unknown case \"{}\"", s)),
+        }
+    }
+}
+
+/// The columns a wordlist may contain.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Column {
+    Root,
+    Category,
+    Definition,
+    Tags,
+    Example,
+    Translation,
+    Notes,
+    Audio,
+    Image,
+    Lesson,
+    Deck,
+    BinId,
+    Id,
+}
+
+impl Column {
+    /// The standard name of the column, as used in header rows.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Root => "root",
+            Column::Category => "category",
+            Column::Definition => "definition",
+            Column::Tags => "tags",
+            Column::Example => "example",
+            Column::Translation => "translation",
+            Column::Notes => "notes",
+            Column::Audio => "audio",
+            Column::Image => "image",
+            Column::Lesson => "lesson",
+            Column::Deck => "deck",
+            Column::BinId => "bin_id",
+            Column::Id => "id",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "root" | "word" | "lemma" => Ok(Column::Root),
+            "category" | "pos" | "part of speech" => Ok(Column::Category),
+            "definition" | "meaning" | "gloss" => Ok(Column::Definition),
+            "tags" | "tag" => Ok(Column::Tags),
+            "example" => Ok(Column::Example),
+            "translation" => Ok(Column::Translation),
+            "notes" => Ok(Column::Notes),
+            "audio" => Ok(Column::Audio),
+            "image" => Ok(Column::Image),
+            "lesson" => Ok(Column::Lesson),
+            "deck" => Ok(Column::Deck),
+            "bin_id" | "bin id" => Ok(Column::BinId),
+            "id" | "guid" | "note id" | "note_id" => Ok(Column::Id),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Header names for columns, for wordlists whose header row doesn't use the
+/// standard names. Parsed from a list like
+/// "word=Icelandic, category=POS, definition=English".
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ColumnMapping {
+    names: Vec<(Column, String)>,
+}
+
+impl ColumnMapping {
+    fn column(&self, header: &str) -> Option<Column> {
+        self.names
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(header.trim()))
+            .map(|&(column, _)| column)
+    }
+}
+
+impl FromStr for ColumnMapping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut names = Vec::new();
+
+        for pair in s.split(',').filter(|pair| !pair.trim().is_empty()) {
+            match pair.split_once('=') {
+                Some((column, name)) => match column.parse::<Column>() {
+                    Ok(column) => names.push((column, name.trim().to_string())),
+                    Err(_) => return Err(format!("unknown column \"{}\"", column.trim())),
+                },
+                None => return Err(format!("expected column=header, found \"{}\"", pair.trim())),
+            }
+        }
+
+        Ok(ColumnMapping { names })
+    }
+}
+
+/// The column order of a wordlist without a header row.
+const POSITIONAL_COLUMNS: [Column; 13] = [
+    Column::Root,
+    Column::Category,
+    Column::Definition,
+    Column::Tags,
+    Column::Example,
+    Column::Translation,
+    Column::Notes,
+    Column::Audio,
+    Column::Image,
+    Column::Lesson,
+    Column::Deck,
+    Column::BinId,
+    Column::Id,
+];
+
+/// Which column each field of a wordlist row holds.
+struct Layout {
+    columns: Vec<Option<Column>>,
+}
+
+impl Layout {
+    fn positional() -> Self {
+        Layout { columns: POSITIONAL_COLUMNS.iter().map(|&c| Some(c)).collect() }
+    }
+
+    /// Treat a row as a header if it names the root column. Names are looked
+    /// up in `mapping` first, then among the standard column names. Columns
+    /// with any other name are ignored. A row with a category in its second
+    /// field is a word, as in "orð, noun, word", however its other fields
+    /// read as column names.
+    fn from_header(record: &StringRecord, mapping: &ColumnMapping) -> Option<Self> {
+        if record.get(1).is_some_and(|category| category.trim().parse::<Category>().is_ok()) {
+            return None;
+        }
+
+        let columns = record
+            .iter()
+            .map(|name| mapping.column(name).or_else(|| name.parse().ok()))
+            .collect::<Vec<Option<Column>>>();
+
+        if columns.contains(&Some(Column::Root)) {
+            Some(Layout { columns })
+        } else {
+            None
+        }
+    }
+
+    /// Get the trimmed, non-empty value of a column.
+    fn get<'r>(&self, record: &'r StringRecord, column: Column) -> Option<&'r str> {
+        let index = self.columns.iter().position(|&c| c == Some(column))?;
+        record.get(index).map(|value| value.trim()).filter(|value| !value.is_empty())
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Example {
+    pub text: String,
+    pub translation: Option<String>,
+}
+
+/// One meaning of a word. A root may be listed several times with the same
+/// category, once for each sense.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Sense {
+    pub definition: String,
+    pub tags: Vec<String>,
+    pub examples: Vec<Example>,
+    /// Cases governed by the word in this sense, for prepositions and verbs.
+    pub governs: Vec<Case>,
+    pub notes: Option<String>,
+    pub audio: Option<String>,
+    pub image: Option<String>,
+    pub lesson: Option<u32>,
+    /// A subdeck path, such as "Chapter 1::Food".
+    pub deck: Option<String>,
+    /// The wordlist section the word was listed under, such as
+    /// "Chapter 1::Food".
+    pub section: Option<String>,
+    /// An identifier for the note, which keeps it the same note in Anki
+    /// however the word changes.
+    pub id: Option<String>,
+    pub origin: Origin,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Dictionary {
-    pub entries: BTreeMap<DictionaryKey, String>,
+    /// Words in the order they were first listed.
+    pub entries: IndexMap<DictionaryKey, Vec<Sense>>,
+    /// Problems found while loading the wordlist.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Finds the categories that a word listed without one could belong to,
+/// given its root and BÍN id.
+pub type Classifier<'a> = &'a dyn Fn(&str, Option<u64>) -> Vec<Category>;
+
+/// The state of reading a wordlist and the files it includes.
+struct Loader<'a> {
+    mapping: &'a ColumnMapping,
+    classify: Classifier<'a>,
+    /// The files that are currently being read, to catch files that include
+    /// themselves.
+    including: Vec<PathBuf>,
+    /// The file being read, as it was named.
+    file: PathBuf,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Loader<'a> {
+    fn new(mapping: &'a ColumnMapping, classify: Classifier<'a>) -> Self {
+        Loader {
+            mapping,
+            classify,
+            including: Vec::new(),
+            file: PathBuf::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn origin(&self, line: Option<u64>) -> Origin {
+        Origin { file: self.file.clone(), line }
+    }
+
+    fn report(&mut self, line: Option<u64>, message: String) {
+        self.diagnostics.push(Diagnostic { origin: self.origin(line), message });
+    }
+
+    /// The lesson number of a word, reporting a lesson that isn't a number,
+    /// such as "Lesson 3", which is left out.
+    fn lesson(&mut self, line: Option<u64>, root: &str, lesson: &str) -> Option<u32> {
+        lesson.trim().parse().ok().or_else(|| {
+            let message = format!("the lesson \"{}\" for {} is not a number", lesson, root);
+            self.report(line, message);
+            None
+        })
+    }
+
+    /// The category of a word listed without one, if there's exactly one it
+    /// could belong to.
+    fn infer_category(
+        &mut self,
+        line: Option<u64>,
+        root: &str,
+        bin_id: Option<u64>,
+    ) -> Option<Category> {
+        match (self.classify)(root, bin_id).as_slice() {
+            [category] => Some(category.clone()),
+            [] => {
+                let message = format!("no category given for {}, and none found in BÍN", root);
+                self.report(line, message);
+                None
+            }
+            categories => {
+                let names: Vec<&str> = categories.iter().map(Category::name).collect();
+                let message = format!(
+                    "no category given for {}, which could be any of: {}",
+                    root,
+                    names.join(", ")
+                );
+                self.report(line, message);
+                None
+            }
+        }
+    }
+
+    /// Report whitespace in a field that is likely a mistake: spaces around
+    /// the value, which are trimmed but often come from copying and pasting,
+    /// and invisible or non-breaking spaces, which are not. Roots also
+    /// shouldn't have several spaces in a row, which would keep them from
+    /// being found in BÍN.
+    fn check_whitespace(&mut self, line: Option<u64>, column: Column, value: &str) {
+        let problem = if value.chars().any(|c| c == '\u{a0}' || c == '\u{200b}' || c == '\u{feff}')
+        {
+            Some("an invisible or non-breaking space")
+        } else if value.trim().is_empty() {
+            None
+        } else if value.trim() != value {
+            Some("spaces around it")
+        } else if column == Column::Root && value.contains("  ") {
+            Some("several spaces in a row")
+        } else {
+            None
+        };
+
+        if let Some(problem) = problem {
+            let message = format!("the {} \"{}\" has {}", column.name(), value, problem);
+            self.report(line, message);
+        }
+    }
+}
+
+/// Where a word was listed: the wordlist file, and the line for delimited
+/// wordlists.
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Origin {
+    pub file: PathBuf,
+    pub line: Option<u64>,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}", self.file.display(), line),
+            None => write!(f, "{}", self.file.display()),
+        }
+    }
+}
+
+/// A problem with a wordlist, and where it was found.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub origin: Origin,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.origin, self.message)
+    }
 }
 
 impl Dictionary {
-    /// Import a set of words into a dictionary, returning the number of entries added.
-    pub fn load<T>(wordlist: T) -> Result<Self, ProgramError>
+    /// Import a delimited wordlist from a reader. Included files are found
+    /// relative to the current directory.
+    #[cfg(test)]
+    pub fn load<T>(wordlist: T, mapping: &ColumnMapping) -> Result<Self, ProgramError>
     where
-        T: std::io::Read,
+        T: Read,
     {
-        let mut dictionary = Dictionary { entries: BTreeMap::new() };
+        let mut dictionary = Dictionary::default();
+        let mut loader = Loader::new(mapping, &|_, _| Vec::new());
+        dictionary.read_delimited(wordlist, &mut loader, Path::new(""), &[])?;
+        dictionary.diagnostics = loader.diagnostics;
+        Ok(dictionary)
+    }
+
+    /// Import a structured wordlist from a reader. Included files are found
+    /// relative to the current directory.
+    #[cfg(test)]
+    pub fn load_structured<T>(wordlist: T, format: Format) -> Result<Self, ProgramError>
+    where
+        T: Read,
+    {
+        let mut dictionary = Dictionary::default();
+        let mapping = ColumnMapping::default();
+        let mut loader = Loader::new(&mapping, &|_, _| Vec::new());
+        dictionary.read_structured(wordlist, format, &mut loader, Path::new(""), &[])?;
+        dictionary.diagnostics = loader.diagnostics;
+        Ok(dictionary)
+    }
+
+    /// Import a wordlist file, choosing the format from its extension.
+    /// Included files are found relative to the file that includes them.
+    /// Words listed without a category are given the only one `classify`
+    /// finds for them. Words that can't be read, such as those with unknown
+    /// categories, are skipped, and reported in `diagnostics` along with
+    /// other problems found along the way.
+    pub fn from_path(
+        path: &Path,
+        mapping: &ColumnMapping,
+        classify: Classifier,
+    ) -> Result<Self, ProgramError> {
+        let mut dictionary = Dictionary::default();
+        let mut loader = Loader::new(mapping, classify);
+        dictionary.read_file(path, &mut loader, &[])?;
+        dictionary.diagnostics = loader.diagnostics;
+        Ok(dictionary)
+    }
+
+    /// Read a wordlist file into the dictionary. `sections` is the section
+    /// the file is included from.
+    fn read_file(
+        &mut self,
+        path: &Path,
+        loader: &mut Loader,
+        sections: &[String],
+    ) -> Result<(), ProgramError> {
+        let cannot_read =
+            |e: std::io::Error| ProgramError::Dictionary(format!("{}: {}", path.display(), e));
+
+        let canonical = path.canonicalize().map_err(cannot_read)?;
+        if loader.including.contains(&canonical) {
+            return Err(ProgramError::Dictionary(format!(
+                "{}: the file includes itself",
+                path.display()
+            )));
+        }
+
+        let file = File::open(path).map_err(cannot_read)?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        loader.including.push(canonical);
+        let including_file = std::mem::replace(&mut loader.file, path.to_path_buf());
+        let result = match Format::from_path(path) {
+            Some(format) => self.read_structured(file, format, loader, dir, sections),
+            None => self.read_delimited(file, loader, dir, sections),
+        };
+        loader.file = including_file;
+        loader.including.pop();
+
+        result
+    }
+
+    /// Import a set of words into the dictionary.
+    ///
+    /// Rows hold a root, its category, and optionally a definition, Anki
+    /// tags, an example sentence and its translation, notes, audio and image
+    /// file names, and a lesson number, in that order. A header row naming
+    /// the columns allows any order, and any subset of the optional columns.
+    /// Rows sharing a root and category are kept as separate senses of the
+    /// same word.
+    ///
+    /// Fields may be separated by tabs, commas, or semicolons, and quoted, as
+    /// exported by spreadsheet programs.
+    ///
+    /// Lines starting with `#` are comments, except for `## Section` headers,
+    /// which apply to the rows that follow them. Deeper headers (`### Part`)
+    /// make nested sections. A line `include other.tsv` reads another
+    /// wordlist in its place, relative to `dir`.
+    fn read_delimited<T>(
+        &mut self,
+        wordlist: T,
+        loader: &mut Loader,
+        dir: &Path,
+        sections: &[String],
+    ) -> Result<(), ProgramError>
+    where
+        T: Read,
+    {
+        let mut text = String::new();
+        BufReader::new(wordlist).read_to_string(&mut text)?;
+        let text = text.trim_start_matches('\u{feff}');
+
+        // Directives are blanked out before the rows are parsed, so that
+        // line numbers stay the same. Each row then takes the section in
+        // force at its line, and includes are read between the rows around
+        // them.
+        let mut section_path = sections.to_vec();
+        let mut line_sections = Vec::new();
+        let mut includes = Vec::new();
+        let mut rows = String::with_capacity(text.len());
+
+        for (line_number, line) in (1u64..).zip(text.lines()) {
+            match Directive::parse(line) {
+                Some(Directive::Section(level, title)) => {
+                    section_path.truncate(sections.len() + level - 1);
+                    if !title.is_empty() {
+                        section_path.push(title.to_string());
+                    }
+                }
+                Some(Directive::Include(file)) => {
+                    includes.push((line_number, dir.join(file), section_path.clone()))
+                }
+                Some(Directive::Comment) => {}
+                None => rows.push_str(line),
+            }
+            rows.push('\n');
+            line_sections.push(section_path.join("::"));
+        }
 
         let mut reader = ReaderBuilder::new()
             .has_headers(false)
-            .delimiter(b'\t')
+            .delimiter(detect_delimiter(&rows))
             .flexible(true)
-            .from_reader(wordlist);
+            .from_reader(rows.as_bytes());
+
+        let mut layout = None;
+        let mut includes = includes.into_iter().peekable();
+
+        for result in reader.records() {
+            let record = match result {
+                Ok(record) => record,
+                Err(e) => {
+                    let line = e.position().map(|position| position.line());
+                    loader.report(line, format!("unreadable row: {}", e));
+                    continue;
+                }
+            };
+
+            // A record's position includes the blank lines before it.
+            let line_number = record.position().map_or(0, |position| {
+                let blank_lines = rows[position.byte() as usize..]
+                    .bytes()
+                    .take_while(|&b| b == b'\n' || b == b'\r')
+                    .filter(|&b| b == b'\n')
+                    .count();
+                position.line() + blank_lines as u64
+            });
 
-        for record in reader.records().flatten() {
-            if let (Some(root), Some(category)) = (record.get(0), record.get(1)) {
-                if let Ok(found_category) = Category::from_str(category) {
-                    let key = DictionaryKey { root: root.to_string(), category: found_category };
+            while let Some((_, path, sections)) =
+                includes.next_if(|(include_line, _, _)| *include_line < line_number)
+            {
+                self.read_file(&path, loader, &sections)?;
+            }
 
-                    let definition = record.get(2).unwrap_or("—");
+            let layout = match &layout {
+                Some(layout) => layout,
+                None => match Layout::from_header(&record, loader.mapping) {
+                    Some(header) => {
+                        layout = Some(header);
+                        continue;
+                    }
+                    None if loader.mapping.names.is_empty() => {
+                        layout.get_or_insert_with(Layout::positional)
+                    }
+                    None => {
+                        return Err(ProgramError::Dictionary(
+                            "the first row is not a header naming the mapped columns".to_string(),
+                        ))
+                    }
+                },
+            };
 
-                    dictionary.entries.insert(key, definition.to_string());
+            for (&column, value) in layout.columns.iter().zip(record.iter()) {
+                if let Some(column) = column {
+                    loader.check_whitespace(Some(line_number), column, value);
+                }
+            }
+
+            if let Some(root) = layout.get(&record, Column::Root) {
+                let bin_id = layout.get(&record, Column::BinId).and_then(|id| id.parse().ok());
+
+                let category = match layout.get(&record, Column::Category) {
+                    Some(category) => Category::from_str(category).ok().or_else(|| {
+                        let message = format!("unknown category \"{}\" for {}", category, root);
+                        loader.report(Some(line_number), message);
+                        None
+                    }),
+                    None => loader.infer_category(Some(line_number), root, bin_id),
+                };
+
+                if let Some(category) = category {
+                    let key = DictionaryKey { root: root.to_string(), category, bin_id };
+
+                    let value = |column| layout.get(&record, column).map(|v| v.to_string());
+
+                    // Anki tags may not contain spaces, so accept either
+                    // spaces or commas between them.
+                    let tags = layout
+                        .get(&record, Column::Tags)
+                        .unwrap_or("")
+                        .split(|c: char| c.is_whitespace() || c == ',')
+                        .filter(|t| !t.is_empty())
+                        .map(|t| t.to_string())
+                        .collect();
+
+                    let section = line_sections
+                        .get(line_number as usize - 1)
+                        .filter(|section| !section.is_empty())
+                        .cloned();
+
+                    let sense = Sense {
+                        definition: value(Column::Definition).unwrap_or_default(),
+                        tags,
+                        examples: value(Column::Example)
+                            .map(|text| Example { text, translation: value(Column::Translation) })
+                            .into_iter()
+                            .collect(),
+                        governs: Vec::new(),
+                        notes: value(Column::Notes),
+                        audio: value(Column::Audio),
+                        image: value(Column::Image),
+                        lesson: layout
+                            .get(&record, Column::Lesson)
+                            .and_then(|l| loader.lesson(Some(line_number), root, l)),
+                        deck: value(Column::Deck),
+                        section,
+                        id: value(Column::Id),
+                        origin: loader.origin(Some(line_number)),
+                    };
+
+                    self.entries.entry(key).or_default().push(sense);
                 }
             }
         }
 
-        Ok(dictionary)
+        for (_, path, sections) in includes {
+            self.read_file(&path, loader, &sections)?;
+        }
+
+        Ok(())
+    }
+
+    /// Import a wordlist in one of the structured formats. The file holds a
+    /// list of `words`, each with a `root` and `category`, and either a
+    /// `definition` or a list of `senses`. It may also `include` a list of
+    /// other wordlists, relative to `dir`.
+    fn read_structured<T>(
+        &mut self,
+        wordlist: T,
+        format: Format,
+        loader: &mut Loader,
+        dir: &Path,
+        sections: &[String],
+    ) -> Result<(), ProgramError>
+    where
+        T: Read,
+    {
+        let mut text = String::new();
+        BufReader::new(wordlist).read_to_string(&mut text)?;
+
+        let record: WordlistRecord = match format {
+            Format::Toml => toml::from_str(&text).map_err(|e| e.to_string()),
+            Format::Yaml => serde_yaml::from_str(&text).map_err(|e| e.to_string()),
+            Format::Json => serde_json::from_str(&text).map_err(|e| e.to_string()),
+        }
+        .map_err(ProgramError::Dictionary)?;
+
+        for word in record.words {
+            loader.check_whitespace(None, Column::Root, &word.root);
+
+            let category = match &word.category {
+                Some(category) => Category::from_str(category).ok().or_else(|| {
+                    let message = format!("unknown category \"{}\" for {}", category, word.root);
+                    loader.report(None, message);
+                    None
+                }),
+                None => loader.infer_category(None, &word.root, word.bin_id),
+            };
+
+            let category = match category {
+                Some(category) => category,
+                None => continue,
+            };
+
+            let lesson = match &word.lesson {
+                Some(LessonRecord::Number(lesson)) => Some(*lesson),
+                Some(LessonRecord::Text(lesson)) => loader.lesson(None, &word.root, lesson),
+                None => None,
+            };
+
+            let (key, mut senses) =
+                word.into_entry(category, lesson).map_err(ProgramError::Dictionary)?;
+            // A word's own section is nested in the section it's included from.
+            for sense in &mut senses {
+                let path: Vec<&str> =
+                    sections.iter().map(String::as_str).chain(sense.section.as_deref()).collect();
+                sense.section = Some(path.join("::")).filter(|path| !path.is_empty());
+                sense.origin = loader.origin(None);
+            }
+            self.entries.entry(key).or_default().extend(senses);
+        }
+
+        for file in record.include {
+            self.read_file(&dir.join(file), loader, sections)?;
+        }
+
+        Ok(())
     }
 }
 
+/// The Icelandic alphabet, including the letters only found in loanwords.
+const ALPHABET: &str = "aábcdðeéfghiíjklmnoópqrstuúvwxyýzþæö";
+
+/// A key for sorting words in Icelandic alphabetical order, where accented
+/// vowels are letters of their own, and þ, æ, and ö come after z. Case is
+/// ignored, and other characters sort after the alphabet.
+pub fn collation_key(word: &str) -> Vec<u32> {
+    word.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match ALPHABET.chars().position(|letter| letter == c) {
+            Some(position) => position as u32,
+            None => ALPHABET.len() as u32 + c as u32,
+        })
+        .collect()
+}
+
+/// A line in a delimited wordlist that isn't a row of data.
+#[derive(Debug, Eq, PartialEq)]
+enum Directive<'a> {
+    Comment,
+    /// A section header, with its level (1 for `##`) and title. A header
+    /// without a title ends the section.
+    Section(usize, &'a str),
+    Include(&'a str),
+}
+
+impl<'a> Directive<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let line = line.trim();
+
+        if let Some(header) = line.strip_prefix("##") {
+            let level = header.chars().take_while(|&c| c == '#').count();
+            Some(Directive::Section(level + 1, header[level..].trim()))
+        } else if line.starts_with('#') {
+            Some(Directive::Comment)
+        } else {
+            let file = line.strip_prefix("include ")?.trim().trim_matches('"');
+            Some(Directive::Include(file))
+        }
+    }
+}
+
+/// Structured wordlist formats, which hold one record per word.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl Format {
+    /// The structured format of a wordlist file, from its extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "toml" => Some(Format::Toml),
+            "yaml" | "yml" => Some(Format::Yaml),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WordlistRecord {
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    words: Vec<WordRecord>,
+}
+
+/// A word in a structured wordlist. A word with a single sense may give its
+/// definition directly, otherwise it lists its senses. Tags apply to every
+/// sense, while examples, governed cases, and notes apply to the senses that
+/// don't have their own.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WordRecord {
+    root: String,
+    category: Option<String>,
+    definition: Option<String>,
+    #[serde(default)]
+    senses: Vec<SenseRecord>,
+    #[serde(default)]
+    tags: Vec<String>,
+    bin_id: Option<u64>,
+    #[serde(default)]
+    examples: Vec<ExampleRecord>,
+    #[serde(default)]
+    governs: Vec<String>,
+    notes: Option<String>,
+    audio: Option<String>,
+    image: Option<String>,
+    lesson: Option<LessonRecord>,
+    deck: Option<String>,
+    section: Option<String>,
+    id: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SenseRecord {
+    definition: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    examples: Vec<ExampleRecord>,
+    #[serde(default)]
+    governs: Vec<String>,
+    notes: Option<String>,
+    id: Option<String>,
+}
+
+/// A lesson is a number, which may be written as text, as in "3".
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LessonRecord {
+    Number(u32),
+    Text(String),
+}
+
+/// An example is either just a sentence, or a sentence and its translation.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ExampleRecord {
+    Text(String),
+    Translated { text: String, translation: Option<String> },
+}
+
+impl From<ExampleRecord> for Example {
+    fn from(record: ExampleRecord) -> Self {
+        match record {
+            ExampleRecord::Text(text) => Example { text, translation: None },
+            ExampleRecord::Translated { text, translation } => Example { text, translation },
+        }
+    }
+}
+
+impl WordRecord {
+    /// The entry of the word, with its category and lesson number, which
+    /// are read separately.
+    fn into_entry(
+        self,
+        category: Category,
+        lesson: Option<u32>,
+    ) -> Result<(DictionaryKey, Vec<Sense>), String> {
+        let WordRecord {
+            root,
+            category: _,
+            definition,
+            senses,
+            tags,
+            bin_id,
+            examples,
+            governs,
+            notes,
+            audio,
+            image,
+            lesson: _,
+            deck,
+            section,
+            id,
+        } = self;
+
+        let senses = match (definition, senses.is_empty()) {
+            (Some(_), false) => {
+                return Err(format!("{}: give either a definition or senses, not both", root))
+            }
+            (definition, true) => vec![SenseRecord {
+                definition: definition.unwrap_or_default(),
+                tags: Vec::new(),
+                examples: Vec::new(),
+                governs: Vec::new(),
+                notes: None,
+                id: None,
+            }],
+            (None, false) => senses,
+        };
+
+        let parse_cases = |cases: &[String]| {
+            cases
+                .iter()
+                .map(|c| c.parse())
+                .collect::<Result<Vec<Case>, String>>()
+                .map_err(|e| format!("{}: {}", root, e))
+        };
+        let governs = parse_cases(&governs)?;
+        let examples: Vec<Example> = examples.into_iter().map(Example::from).collect();
+
+        let mut result = Vec::new();
+        for sense in senses {
+            let sense_governs = parse_cases(&sense.governs)?;
+
+            result.push(Sense {
+                definition: sense.definition,
+                tags: tags.iter().chain(sense.tags.iter()).cloned().collect(),
+                examples: if sense.examples.is_empty() {
+                    examples.clone()
+                } else {
+                    sense.examples.into_iter().map(Example::from).collect()
+                },
+                governs: if sense_governs.is_empty() { governs.clone() } else { sense_governs },
+                notes: sense.notes.or_else(|| notes.clone()),
+                audio: audio.clone(),
+                image: image.clone(),
+                lesson,
+                deck: deck.clone(),
+                section: section.clone(),
+                id: sense.id.or_else(|| id.clone()),
+                origin: Origin::default(),
+            });
+        }
+
+        Ok((DictionaryKey { root, category, bin_id }, result))
+    }
+}
+
+/// Guess the field delimiter from the first line of a wordlist: whichever of
+/// tab, comma, or semicolon appears most often outside of quotes, preferring
+/// tabs.
+fn detect_delimiter(text: &str) -> u8 {
+    let first_line = text.lines().find(|line| !line.trim().is_empty()).unwrap_or("");
+
+    let mut quoted = false;
+    let mut counts = [(b'\t', 0), (b',', 0), (b';', 0)];
+
+    for c in first_line.bytes() {
+        if c == b'"' {
+            quoted = !quoted;
+        } else if !quoted {
+            for (delimiter, count) in counts.iter_mut() {
+                if c == *delimiter {
+                    *count += 1;
+                }
+            }
+        }
+    }
+
+    // max_by_key returns the last maximum, so search in reverse to prefer tabs.
+    counts.iter().rev().max_by_key(|&&(_, count)| count).map(|&(d, _)| d).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,36 +1052,428 @@ bar	verb	definition of bar
 baz	adjective	definition of baz"#
             .as_bytes();
 
-        let dictionary = Dictionary::load(wordlist).unwrap();
+        let dictionary = Dictionary::load(wordlist, &ColumnMapping::default()).unwrap();
 
         assert_eq!(
             "definition of foo",
             dictionary
                 .entries
-                .get(&DictionaryKey { root: "foo".to_string(), category: Category::Noun })
-                .unwrap()
+                .get(&DictionaryKey {
+                    root: "foo".to_string(),
+                    category: Category::Noun,
+                    bin_id: None
+                })
+                .unwrap()[0]
+                .definition
         );
 
         assert_eq!(
             "definition of bar",
             dictionary
                 .entries
-                .get(&DictionaryKey { root: "bar".to_string(), category: Category::Verb })
-                .unwrap()
+                .get(&DictionaryKey {
+                    root: "bar".to_string(),
+                    category: Category::Verb,
+                    bin_id: None
+                })
+                .unwrap()[0]
+                .definition
         );
 
         assert_eq!(
             "definition of baz",
             dictionary
                 .entries
-                .get(&DictionaryKey { root: "baz".to_string(), category: Category::Adjective })
-                .unwrap()
+                .get(&DictionaryKey {
+                    root: "baz".to_string(),
+                    category: Category::Adjective,
+                    bin_id: None
+                })
+                .unwrap()[0]
+                .definition
         );
 
         // Shouldn't find a non-existent entry
-        assert!(dictionary
+        assert!(!dictionary.entries.contains_key(&DictionaryKey {
+            root: "baz".to_string(),
+            category: Category::Noun,
+            bin_id: None
+        }));
+    }
+
+    #[test]
+    pub fn keeps_every_sense() {
+        let wordlist = "hestur\tnoun\thorse\tanimals farm\tHesturinn er stór.
+hestur\tnoun\tpommel horse\tsport
+hestur\tverb\tnot a verb"
+            .as_bytes();
+
+        let dictionary = Dictionary::load(wordlist, &ColumnMapping::default()).unwrap();
+
+        let senses = dictionary
+            .entries
+            .get(&DictionaryKey {
+                root: "hestur".to_string(),
+                category: Category::Noun,
+                bin_id: None,
+            })
+            .unwrap();
+
+        assert_eq!(2, senses.len());
+        assert_eq!("horse", senses[0].definition);
+        assert_eq!(vec!["animals", "farm"], senses[0].tags);
+        assert_eq!(
+            vec![Example { text: "Hesturinn er stór.".to_string(), translation: None }],
+            senses[0].examples
+        );
+        assert_eq!("pommel horse", senses[1].definition);
+        assert_eq!(vec!["sport"], senses[1].tags);
+        assert!(senses[1].examples.is_empty());
+        assert_eq!(2, dictionary.entries.len());
+    }
+
+    #[test]
+    pub fn reads_named_columns() {
+        let wordlist = "lesson\troot\tcategory\taudio\tdefinition\tguid
+3\thestur\tnoun\thestur.mp3\thorse\tio1-hestur
+x\tlæra\tverb\t\tto learn"
+            .as_bytes();
+
+        let dictionary = Dictionary::load(wordlist, &ColumnMapping::default()).unwrap();
+
+        let hestur = &dictionary
+            .entries
+            .get(&DictionaryKey {
+                root: "hestur".to_string(),
+                category: Category::Noun,
+                bin_id: None,
+            })
+            .unwrap()[0];
+
+        assert_eq!("horse", hestur.definition);
+        assert_eq!(Some(3), hestur.lesson);
+        assert_eq!(Some("hestur.mp3".to_string()), hestur.audio);
+        assert!(hestur.examples.is_empty());
+        assert_eq!(Some("io1-hestur".to_string()), hestur.id);
+
+        let laera = &dictionary
+            .entries
+            .get(&DictionaryKey {
+                root: "læra".to_string(),
+                category: Category::Verb,
+                bin_id: None,
+            })
+            .unwrap()[0];
+
+        assert_eq!("to learn", laera.definition);
+        assert_eq!(None, laera.lesson);
+        assert_eq!(1, dictionary.diagnostics.len());
+        assert_eq!("the lesson \"x\" for læra is not a number", dictionary.diagnostics[0].message);
+        assert_eq!(None, laera.audio);
+        assert_eq!(None, laera.id);
+    }
+
+    #[test]
+    pub fn reads_words_named_like_columns() {
+        let wordlist = "orð\tnoun\tword\nrót\tnoun\troot".as_bytes();
+        let dictionary = Dictionary::load(wordlist, &ColumnMapping::default()).unwrap();
+
+        let ord = &dictionary
+            .entries
+            .get(&DictionaryKey {
+                root: "orð".to_string(), category: Category::Noun, bin_id: None
+            })
+            .unwrap()[0];
+
+        assert_eq!("word", ord.definition);
+        assert_eq!(2, dictionary.entries.len());
+    }
+
+    #[test]
+    pub fn reads_mapped_spreadsheet_export() {
+        let wordlist = "\u{feff}English,POS,Icelandic,Lesson
+\"light, brightness\",noun,birta,1
+\"to learn, study\",verb,læra,2"
+            .as_bytes();
+
+        let mapping: ColumnMapping =
+            "word=Icelandic, category=POS, definition=English".parse().unwrap();
+        let dictionary = Dictionary::load(wordlist, &mapping).unwrap();
+
+        let birta = &dictionary
+            .entries
+            .get(&DictionaryKey {
+                root: "birta".to_string(),
+                category: Category::Noun,
+                bin_id: None,
+            })
+            .unwrap()[0];
+        assert_eq!("light, brightness", birta.definition);
+        assert_eq!(Some(1), birta.lesson);
+
+        let laera = &dictionary
             .entries
-            .get(&DictionaryKey { root: "baz".to_string(), category: Category::Noun })
-            .is_none());
+            .get(&DictionaryKey {
+                root: "læra".to_string(),
+                category: Category::Verb,
+                bin_id: None,
+            })
+            .unwrap()[0];
+        assert_eq!("to learn, study", laera.definition);
+
+        // A mapping requires a header row.
+        let wordlist = "birta,noun,light".as_bytes();
+        assert!(Dictionary::load(wordlist, &mapping).is_err());
+
+        assert!("word".parse::<ColumnMapping>().is_err());
+        assert!("colour=Litur".parse::<ColumnMapping>().is_err());
+    }
+
+    #[test]
+    pub fn loads_structured_wordlists() {
+        let toml = r#"
+[[words]]
+root = "hestur"
+category = "noun"
+definition = "horse"
+tags = ["animals"]
+examples = [{ text = "Hesturinn er stór.", translation = "The horse is big." }]
+
+[[words]]
+root = "á"
+category = "preposition"
+governs = ["acc"]
+examples = ["Ég fer á fjallið."]
+
+[[words.senses]]
+definition = "onto"
+
+[[words.senses]]
+definition = "on"
+governs = ["dat"]
+examples = ["Bókin er á borðinu."]
+"#;
+
+        let yaml = r#"
+words:
+  - root: hestur
+    category: noun
+    definition: horse
+    tags: [animals]
+    examples:
+      - text: Hesturinn er stór.
+        translation: The horse is big.
+  - root: á
+    category: preposition
+    governs: [acc]
+    examples: [Ég fer á fjallið.]
+    senses:
+      - definition: onto
+      - definition: "on"
+        governs: [dat]
+        examples: [Bókin er á borðinu.]
+"#;
+
+        let json = r#"{"words": [
+    {"root": "hestur", "category": "noun", "definition": "horse", "tags": ["animals"],
+     "examples": [{"text": "Hesturinn er stór.", "translation": "The horse is big."}]},
+    {"root": "á", "category": "preposition", "governs": ["acc"],
+     "examples": ["Ég fer á fjallið."],
+     "senses": [{"definition": "onto"},
+                {"definition": "on", "governs": ["dat"], "examples": ["Bókin er á borðinu."]}]}
+]}"#;
+
+        for (text, format) in &[(toml, Format::Toml), (yaml, Format::Yaml), (json, Format::Json)] {
+            let dictionary = Dictionary::load_structured(text.as_bytes(), *format).unwrap();
+
+            let hestur = &dictionary
+                .entries
+                .get(&DictionaryKey {
+                    root: "hestur".to_string(),
+                    category: Category::Noun,
+                    bin_id: None,
+                })
+                .unwrap()[0];
+            assert_eq!("horse", hestur.definition);
+            assert_eq!(vec!["animals"], hestur.tags);
+            assert_eq!(Some("The horse is big.".to_string()), hestur.examples[0].translation);
+
+            let a = dictionary
+                .entries
+                .get(&DictionaryKey {
+                    root: "á".to_string(),
+                    category: Category::Preposition,
+                    bin_id: None,
+                })
+                .unwrap();
+            assert_eq!(2, a.len());
+            assert_eq!(vec![Case::Accusative], a[0].governs);
+            assert_eq!("Ég fer á fjallið.", a[0].examples[0].text);
+            assert_eq!(vec![Case::Dative], a[1].governs);
+            assert_eq!("Bókin er á borðinu.", a[1].examples[0].text);
+        }
+
+        let both = r#"{"words": [{"root": "á", "category": "preposition", "definition": "on",
+                                   "senses": [{"definition": "onto"}]}]}"#;
+        assert!(Dictionary::load_structured(both.as_bytes(), Format::Json).is_err());
+
+        let unknown = r#"{"words": [{"root": "á", "category": "particle"}]}"#;
+        let dictionary = Dictionary::load_structured(unknown.as_bytes(), Format::Json).unwrap();
+        assert!(dictionary.entries.is_empty());
+        assert_eq!("unknown category \"particle\" for á", dictionary.diagnostics[0].message);
+        let lessons = r#"{"words": [{"root": "á", "category": "preposition", "lesson": "2"},
+                                      {"root": "í", "category": "preposition", "lesson": "2b"}]}"#;
+        let dictionary = Dictionary::load_structured(lessons.as_bytes(), Format::Json).unwrap();
+        let a = &dictionary.entries.values().next().unwrap()[0];
+        assert_eq!(Some(2), a.lesson);
+        assert_eq!(1, dictionary.diagnostics.len());
+        assert_eq!("the lesson \"2b\" for í is not a number", dictionary.diagnostics[0].message);
+    }
+
+    #[test]
+    pub fn reads_comments_and_sections() {
+        let wordlist = "# Icelandic Online 1
+## Chapter 1
+birta\tnoun\tlight
+### Food
+brauð\tnoun\tbread
+## Chapter 2
+# hestur\tnoun\thorse
+læra\tverb\tto learn
+##
+tala\tverb\tto talk"
+            .as_bytes();
+
+        let dictionary = Dictionary::load(wordlist, &ColumnMapping::default()).unwrap();
+        let section = |root: &str, category| {
+            dictionary.entries[&DictionaryKey { root: root.to_string(), category, bin_id: None }][0]
+                .section
+                .clone()
+        };
+
+        assert_eq!(4, dictionary.entries.len());
+        assert_eq!(Some("Chapter 1".to_string()), section("birta", Category::Noun));
+        assert_eq!(Some("Chapter 1::Food".to_string()), section("brauð", Category::Noun));
+        assert_eq!(Some("Chapter 2".to_string()), section("læra", Category::Verb));
+        assert_eq!(None, section("tala", Category::Verb));
+
+        assert_eq!(Some(Directive::Include("ch1.tsv")), Directive::parse("include \"ch1.tsv\""));
+        assert_eq!(Some(Directive::Section(2, "Food")), Directive::parse("### Food"));
+        assert_eq!(Some(Directive::Comment), Directive::parse("  #birta"));
+        assert_eq!(None, Directive::parse("birta\tnoun\tlight"));
+    }
+
+    #[test]
+    pub fn includes_other_wordlists() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        std::fs::create_dir(dir.join("chapters")).unwrap();
+
+        std::fs::write(
+            dir.join("course.tsv"),
+            "## Chapter 1\ninclude chapters/ch1.tsv\n## Chapter 2\ninclude chapters/ch2.toml\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("chapters/ch1.tsv"),
+            "birta\tnoun\tlight\n## Food\nbrauð\tnoun\tbread\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("chapters/ch2.toml"),
+            "[[words]]\nroot = \"læra\"\ncategory = \"verb\"\ndefinition = \"to learn\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("loop.tsv"), "include loop.tsv\n").unwrap();
+
+        let mapping = ColumnMapping::default();
+        let dictionary =
+            Dictionary::from_path(&dir.join("course.tsv"), &mapping, &|_, _| Vec::new()).unwrap();
+        let section = |root: &str, category| {
+            dictionary.entries[&DictionaryKey { root: root.to_string(), category, bin_id: None }][0]
+                .section
+                .clone()
+        };
+
+        assert_eq!(3, dictionary.entries.len());
+        assert_eq!(Some("Chapter 1".to_string()), section("birta", Category::Noun));
+        assert_eq!(Some("Chapter 1::Food".to_string()), section("brauð", Category::Noun));
+        assert_eq!(Some("Chapter 2".to_string()), section("læra", Category::Verb));
+
+        assert!(Dictionary::from_path(&dir.join("loop.tsv"), &mapping, &|_, _| Vec::new()).is_err());
+    }
+
+    #[test]
+    pub fn keeps_input_order() {
+        let wordlist = "tala\tverb\tto talk
+birta\tnoun\tlight
+læra\tverb\tto learn
+tala\tverb\tto count"
+            .as_bytes();
+
+        let dictionary = Dictionary::load(wordlist, &ColumnMapping::default()).unwrap();
+        let roots: Vec<&str> = dictionary.entries.keys().map(|key| key.root.as_str()).collect();
+        assert_eq!(vec!["tala", "birta", "læra"], roots);
+    }
+
+    #[test]
+    pub fn sorts_icelandic_alphabetically() {
+        let mut words = vec!["öl", "þak", "ás", "Ýsa", "bók", "dalur", "aska", "ys", "ðe", "zebra"];
+        words.sort_by_key(|word| collation_key(word));
+        assert_eq!(
+            vec!["aska", "ás", "bók", "dalur", "ðe", "ys", "Ýsa", "zebra", "þak", "öl"],
+            words
+        );
+    }
+
+    #[test]
+    pub fn infers_missing_categories() {
+        let wordlist = "hestur\t\thorse
+læra
+á\t\ton
+tala\tverbb\tto talk"
+            .as_bytes();
+
+        let classify = |root: &str, _: Option<u64>| match root {
+            "hestur" => vec![Category::Noun],
+            "á" => vec![Category::Noun, Category::Verb, Category::Preposition],
+            _ => Vec::new(),
+        };
+
+        let mut dictionary = Dictionary::default();
+        let mapping = ColumnMapping::default();
+        let mut loader = Loader::new(&mapping, &classify);
+        dictionary.read_delimited(wordlist, &mut loader, Path::new(""), &[]).unwrap();
+
+        // Only hestur is unambiguous. The others are skipped and reported.
+        assert_eq!(3, loader.diagnostics.len());
+        let keys: Vec<&DictionaryKey> = dictionary.entries.keys().collect();
+        assert_eq!(
+            vec![&DictionaryKey {
+                root: "hestur".to_string(),
+                category: Category::Noun,
+                bin_id: None
+            }],
+            keys
+        );
+        assert_eq!("horse", dictionary.entries[0][0].definition);
+
+        assert_eq!(
+            vec![Category::Noun, Category::Verb],
+            Category::from_word_classes(&[("kvk", true), ("so", true), ("kk", true)])
+        );
+        assert_eq!(
+            vec![Category::Noun],
+            Category::from_word_classes(&[("kvk", false), ("kvk", true), ("st", true)])
+        );
+        assert_eq!(vec![Category::ProperNoun], Category::from_word_classes(&[("kvk", false)]));
+    }
+
+    #[test]
+    pub fn detects_delimiter() {
+        assert_eq!(b'\t', detect_delimiter("birta\tnoun\tlight, brightness"));
+        assert_eq!(b',', detect_delimiter("birta,noun,\"light; brightness\""));
+        assert_eq!(b';', detect_delimiter("\n\nbirta;noun;light"));
+        assert_eq!(b'\t', detect_delimiter("birta"));
     }
 }
@@ -0,0 +1,435 @@
+//! Cells of noun, adjective, and verb paradigms, for drill cards that ask
+//! for one form at a time, such as "hestur — dative plural?", and for cloze
+//! cards that blank out some cells of a paradigm table.
+
+use crate::bindata::{AdjectiveEntry, NounEntry, VerbEntry};
+use crate::ids::stable_hash;
+use std::str::FromStr;
+
+/// A kind of word that can be drilled. Each kind has a note type of its own.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DrillKind {
+    Noun,
+    Adjective,
+    Verb,
+}
+
+impl DrillKind {
+    pub const ALL: [DrillKind; 3] = [DrillKind::Noun, DrillKind::Adjective, DrillKind::Verb];
+
+    /// The name of the kind, which selects all of its cells.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DrillKind::Noun => "noun",
+            DrillKind::Adjective => "adjective",
+            DrillKind::Verb => "verb",
+        }
+    }
+}
+
+/// A cell of a paradigm.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cell {
+    pub kind: DrillKind,
+    /// Where the cell is in the kind's paradigm, and in the list of its
+    /// forms.
+    pub index: usize,
+    /// The name the cell is selected by, as in "dat-pl".
+    pub name: String,
+    /// How a card asks for the cell, as in "dative plural".
+    pub label: String,
+    /// The field of the word's note type that holds the form, as in "Dative
+    /// Plural", if the note type's table shows the cell.
+    pub field: Option<String>,
+}
+
+const CASES: [(&str, &str); 4] =
+    [("nom", "nominative"), ("acc", "accusative"), ("dat", "dative"), ("gen", "genitive")];
+const NUMBERS: [(&str, &str); 2] = [("sg", "singular"), ("pl", "plural")];
+const GENDERS: [(&str, &str); 3] = [("masc", "masculine"), ("fem", "feminine"), ("neut", "neuter")];
+const TENSES: [(&str, &str); 2] = [("pres", "present"), ("past", "past")];
+const PERSONS: [(&str, &str, &str); 6] = [
+    ("1sg", "ég", "1st Singular"),
+    ("2sg", "þú", "2nd Singular"),
+    ("3sg", "hann/hún/það", "3rd Singular"),
+    ("1pl", "við", "1st Plural"),
+    ("2pl", "þið", "2nd Plural"),
+    ("3pl", "þeir/þær/þau", "3rd Plural"),
+];
+
+/// Capitalise each word of a label, as the fields of note types are.
+fn title(label: &str) -> String {
+    label
+        .split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// All the cells of a kind's paradigm, in the order of the forms returned
+/// by `noun_forms`, `adjective_forms`, and `verb_forms`.
+pub fn cells(kind: DrillKind) -> Vec<Cell> {
+    let mut cells = Vec::new();
+    let mut push = |name: String, label: String, field: Option<String>| {
+        cells.push(Cell { kind, index: cells.len(), name, label, field });
+    };
+
+    match kind {
+        DrillKind::Noun => {
+            for (definite, definite_label) in [("", ""), ("-def", " definite")] {
+                for (number, number_label) in NUMBERS {
+                    for (case, case_label) in CASES {
+                        let label = format!("{} {}{}", case_label, number_label, definite_label);
+                        let field = Some(title(&label));
+                        push(format!("{}-{}{}", case, number, definite), label, field);
+                    }
+                }
+            }
+        }
+        DrillKind::Adjective => {
+            for (weak, weak_label) in [("", "strong"), ("-weak", "weak")] {
+                for (number, number_label) in NUMBERS {
+                    for (gender, gender_label) in GENDERS {
+                        for (case, case_label) in CASES {
+                            // The adjective table only shows the strong forms.
+                            let field = Some(title(&format!(
+                                "{} {} {}",
+                                gender_label, number_label, case_label
+                            )))
+                            .filter(|_| weak.is_empty());
+                            push(
+                                format!("{}-{}-{}{}", gender, case, number, weak),
+                                format!(
+                                    "{} {} {}, {}",
+                                    gender_label, case_label, number_label, weak_label
+                                ),
+                                field,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        DrillKind::Verb => {
+            for (tense, tense_label) in TENSES {
+                for (person, pronoun, person_field) in PERSONS {
+                    push(
+                        format!("{}-{}", tense, person),
+                        format!("{}, {}", pronoun, tense_label),
+                        Some(format!("{} {}", title(tense_label), person_field)),
+                    );
+                }
+            }
+        }
+    }
+
+    cells
+}
+
+/// Parse a comma-separated list of cells, such as "gen-sg,nom-pl,past-1sg".
+/// The name of a kind, such as "noun", stands for all of its cells.
+pub fn parse_cells(names: &str) -> Result<Vec<Cell>, String> {
+    let mut parsed: Vec<Cell> = Vec::new();
+
+    for name in names.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let selected: Vec<Cell> = match DrillKind::ALL.iter().find(|kind| kind.name() == name) {
+            Some(&kind) => cells(kind),
+            None => DrillKind::ALL
+                .iter()
+                .flat_map(|&kind| cells(kind))
+                .filter(|cell| cell.name == name)
+                .collect(),
+        };
+
+        if selected.is_empty() {
+            return Err(format!("unknown paradigm cell \"{}\"", name));
+        }
+        for cell in selected {
+            if !parsed.contains(&cell) {
+                parsed.push(cell);
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Which cells of a paradigm table a cloze card blanks out.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClozeSelection {
+    /// This many cells, chosen at random for each word.
+    Random(usize),
+    /// The cells matching any of these patterns. A pattern is a part of a
+    /// cell name, or several separated by dashes, as in "dat" for all
+    /// dative cells, or "past-1sg".
+    Patterns(Vec<String>),
+}
+
+impl FromStr for ClozeSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(count) = s.strip_prefix("random") {
+            return match count.strip_prefix(':').unwrap_or(count) {
+                "" => Ok(ClozeSelection::Random(3)),
+                count => match count.parse() {
+                    Ok(count) if count > 0 => Ok(ClozeSelection::Random(count)),
+                    _ => Err(format!("invalid number of cells \"{}\"", count)),
+                },
+            };
+        }
+
+        let patterns: Vec<String> =
+            s.split(',').map(str::trim).filter(|p| !p.is_empty()).map(str::to_string).collect();
+
+        let all: Vec<Cell> = DrillKind::ALL.iter().flat_map(|&kind| cells(kind)).collect();
+        for pattern in &patterns {
+            if !all.iter().any(|cell| matches(cell, pattern)) {
+                return Err(format!("no paradigm cell matches \"{}\"", pattern));
+            }
+        }
+
+        Ok(ClozeSelection::Patterns(patterns))
+    }
+}
+
+/// Whether each dash-separated part of the pattern is a part of the cell's
+/// name.
+fn matches(cell: &Cell, pattern: &str) -> bool {
+    pattern.split('-').all(|part| cell.name.split('-').any(|name_part| name_part == part))
+}
+
+impl ClozeSelection {
+    /// Choose which of the cells to blank out. Only cells shown in the
+    /// table, with a form, can be chosen. Random choices are seeded by
+    /// `seed`, so that a word's card stays the same from one build to the
+    /// next.
+    pub fn select<'a>(
+        &self,
+        cells: &'a [Cell],
+        forms: &[Option<&str>],
+        seed: &str,
+    ) -> Vec<&'a Cell> {
+        let mut candidates: Vec<&Cell> = cells
+            .iter()
+            .filter(|cell| cell.field.is_some())
+            .filter(|cell| forms.get(cell.index).copied().flatten().is_some())
+            .collect();
+
+        match self {
+            ClozeSelection::Patterns(patterns) => {
+                candidates.retain(|cell| patterns.iter().any(|pattern| matches(cell, pattern)));
+                candidates
+            }
+            ClozeSelection::Random(count) => {
+                // A partial Fisher-Yates shuffle, driven by xorshift.
+                let mut state = stable_hash(seed) | 1;
+                let count = (*count).min(candidates.len());
+                for i in 0..count {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    let j = i + (state % (candidates.len() - i) as u64) as usize;
+                    candidates.swap(i, j);
+                }
+                candidates.truncate(count);
+                candidates.sort_by_key(|cell| cell.index);
+                candidates
+            }
+        }
+    }
+}
+
+/// The forms of a noun, in the order of `cells(DrillKind::Noun)`.
+pub fn noun_forms(noun: &NounEntry) -> Vec<Option<&str>> {
+    [
+        &noun.nom_sg,
+        &noun.acc_sg,
+        &noun.dat_sg,
+        &noun.gen_sg,
+        &noun.nom_pl,
+        &noun.acc_pl,
+        &noun.dat_pl,
+        &noun.gen_pl,
+        &noun.nom_sg_def,
+        &noun.acc_sg_def,
+        &noun.dat_sg_def,
+        &noun.gen_sg_def,
+        &noun.nom_pl_def,
+        &noun.acc_pl_def,
+        &noun.dat_pl_def,
+        &noun.gen_pl_def,
+    ]
+    .iter()
+    .map(|form| form.as_deref())
+    .collect()
+}
+
+/// The forms of an adjective, in the order of `cells(DrillKind::Adjective)`.
+pub fn adjective_forms(adjective: &AdjectiveEntry) -> Vec<Option<&str>> {
+    [
+        &adjective.masc_nom_sg_strong,
+        &adjective.masc_acc_sg_strong,
+        &adjective.masc_dat_sg_strong,
+        &adjective.masc_gen_sg_strong,
+        &adjective.fem_nom_sg_strong,
+        &adjective.fem_acc_sg_strong,
+        &adjective.fem_dat_sg_strong,
+        &adjective.fem_gen_sg_strong,
+        &adjective.neut_nom_sg_strong,
+        &adjective.neut_acc_sg_strong,
+        &adjective.neut_dat_sg_strong,
+        &adjective.neut_gen_sg_strong,
+        &adjective.masc_nom_pl_strong,
+        &adjective.masc_acc_pl_strong,
+        &adjective.masc_dat_pl_strong,
+        &adjective.masc_gen_pl_strong,
+        &adjective.fem_nom_pl_strong,
+        &adjective.fem_acc_pl_strong,
+        &adjective.fem_dat_pl_strong,
+        &adjective.fem_gen_pl_strong,
+        &adjective.neut_nom_pl_strong,
+        &adjective.neut_acc_pl_strong,
+        &adjective.neut_dat_pl_strong,
+        &adjective.neut_gen_pl_strong,
+        &adjective.masc_nom_sg_weak,
+        &adjective.masc_acc_sg_weak,
+        &adjective.masc_dat_sg_weak,
+        &adjective.masc_gen_sg_weak,
+        &adjective.fem_nom_sg_weak,
+        &adjective.fem_acc_sg_weak,
+        &adjective.fem_dat_sg_weak,
+        &adjective.fem_gen_sg_weak,
+        &adjective.neut_nom_sg_weak,
+        &adjective.neut_acc_sg_weak,
+        &adjective.neut_dat_sg_weak,
+        &adjective.neut_gen_sg_weak,
+        &adjective.masc_nom_pl_weak,
+        &adjective.masc_acc_pl_weak,
+        &adjective.masc_dat_pl_weak,
+        &adjective.masc_gen_pl_weak,
+        &adjective.fem_nom_pl_weak,
+        &adjective.fem_acc_pl_weak,
+        &adjective.fem_dat_pl_weak,
+        &adjective.fem_gen_pl_weak,
+        &adjective.neut_nom_pl_weak,
+        &adjective.neut_acc_pl_weak,
+        &adjective.neut_dat_pl_weak,
+        &adjective.neut_gen_pl_weak,
+    ]
+    .iter()
+    .map(|form| form.as_deref())
+    .collect()
+}
+
+/// The forms of a verb, in the order of `cells(DrillKind::Verb)`.
+pub fn verb_forms(verb: &VerbEntry) -> Vec<Option<&str>> {
+    [
+        &verb.pres_ind_first_sg,
+        &verb.pres_ind_second_sg,
+        &verb.pres_ind_third_sg,
+        &verb.pres_ind_first_pl,
+        &verb.pres_ind_second_pl,
+        &verb.pres_ind_third_pl,
+        &verb.past_ind_first_sg,
+        &verb.past_ind_second_sg,
+        &verb.past_ind_third_sg,
+        &verb.past_ind_first_pl,
+        &verb.past_ind_second_pl,
+        &verb.past_ind_third_pl,
+    ]
+    .iter()
+    .map(|form| form.as_deref())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn names_cells() {
+        let nouns = cells(DrillKind::Noun);
+        assert_eq!(16, nouns.len());
+        assert_eq!(("dat-pl", "dative plural"), (nouns[6].name.as_str(), nouns[6].label.as_str()));
+        assert_eq!("gen-sg-def", nouns[11].name);
+        assert_eq!(Some("Genitive Singular Definite".to_string()), nouns[11].field);
+
+        let adjectives = cells(DrillKind::Adjective);
+        assert_eq!(48, adjectives.len());
+        assert_eq!("feminine dative singular, strong", adjectives[6].label);
+        assert_eq!(Some("Feminine Singular Dative".to_string()), adjectives[6].field);
+        assert_eq!("neut-gen-pl-weak", adjectives[47].name);
+        assert_eq!(None, adjectives[47].field);
+
+        let verbs = cells(DrillKind::Verb);
+        assert_eq!(12, verbs.len());
+        assert_eq!(("past-1sg", "ég, past"), (verbs[6].name.as_str(), verbs[6].label.as_str()));
+        assert_eq!(Some("Past 1st Singular".to_string()), verbs[6].field);
+    }
+
+    #[test]
+    pub fn selects_cloze_cells() {
+        let nouns = cells(DrillKind::Noun);
+        let mut forms = vec![Some("hestur"); 16];
+        forms[14] = None;
+
+        let dative: ClozeSelection = "dat".parse().unwrap();
+        let names: Vec<&str> =
+            dative.select(&nouns, &forms, "").iter().map(|cell| cell.name.as_str()).collect();
+        assert_eq!(vec!["dat-sg", "dat-pl", "dat-sg-def"], names);
+
+        let random: ClozeSelection = "random:4".parse().unwrap();
+        assert_eq!(ClozeSelection::Random(4), random);
+        let chosen = random.select(&nouns, &forms, "hestur");
+        assert_eq!(4, chosen.len());
+        assert_eq!(chosen, random.select(&nouns, &forms, "hestur"));
+        assert!(chosen.iter().all(|cell| cell.index != 14));
+
+        assert_eq!(Ok(ClozeSelection::Random(3)), "random".parse());
+        assert!("random:0".parse::<ClozeSelection>().is_err());
+        assert!("dative".parse::<ClozeSelection>().is_err());
+    }
+
+    #[test]
+    pub fn parses_cells() {
+        let parsed = parse_cells("gen-sg, nom-pl,past-1sg,gen-sg").unwrap();
+        let names: Vec<&str> = parsed.iter().map(|cell| cell.name.as_str()).collect();
+        assert_eq!(vec!["gen-sg", "nom-pl", "past-1sg"], names);
+        assert_eq!(DrillKind::Verb, parsed[2].kind);
+
+        assert_eq!(12, parse_cells("verb").unwrap().len());
+        assert_eq!(Err("unknown paradigm cell \"gen\"".to_string()), parse_cells("gen"));
+    }
+
+    #[test]
+    pub fn lists_forms_in_cell_order() {
+        let verb = VerbEntry {
+            pres_ind_first_sg: Some("læri".to_string()),
+            pres_ind_second_sg: Some("lærir".to_string()),
+            pres_ind_third_sg: Some("lærir".to_string()),
+            pres_ind_first_pl: Some("lærum".to_string()),
+            pres_ind_second_pl: Some("lærið".to_string()),
+            pres_ind_third_pl: Some("læra".to_string()),
+            past_ind_first_sg: Some("lærði".to_string()),
+            past_ind_second_sg: Some("lærðir".to_string()),
+            past_ind_third_sg: Some("lærði".to_string()),
+            past_ind_first_pl: Some("lærðum".to_string()),
+            past_ind_second_pl: Some("lærðuð".to_string()),
+            past_ind_third_pl: None,
+        };
+
+        let forms = verb_forms(&verb);
+        assert_eq!(cells(DrillKind::Verb).len(), forms.len());
+        assert_eq!(Some("lærði"), forms[6]);
+        assert_eq!(None, forms[11]);
+    }
+}
@@ -0,0 +1,65 @@
+//! Word frequency lists, for ordering a deck by how common its words are.
+
+use crate::ProgramError;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+/// The frequency rank of each word in a list, the most common word first.
+#[derive(Debug, Default)]
+pub struct Frequencies {
+    ranks: HashMap<String, usize>,
+}
+
+impl Frequencies {
+    /// Read a frequency list. Each line holds a word, optionally followed by
+    /// its count, separated by whitespace. If the counts are given the words
+    /// are ranked by them, otherwise by the order of the lines.
+    pub fn load<T>(reader: T) -> Result<Self, ProgramError>
+    where
+        T: Read,
+    {
+        let mut words: Vec<(String, Option<u64>)> = Vec::new();
+
+        for line in BufReader::new(reader).lines() {
+            let line = line?;
+            let mut fields = line.split_whitespace();
+            if let Some(word) = fields.next() {
+                words.push((word.to_string(), fields.next().and_then(|c| c.parse().ok())));
+            }
+        }
+
+        // A stable sort keeps the line order between words with equal counts.
+        words.sort_by_key(|&(_, count)| std::cmp::Reverse(count.unwrap_or(0)));
+
+        let mut ranks = HashMap::new();
+        for (rank, (word, _)) in words.into_iter().enumerate() {
+            ranks.entry(word).or_insert(rank);
+        }
+
+        Ok(Frequencies { ranks })
+    }
+
+    /// The rank of a word, 0 for the most common. Words that aren't in the
+    /// list have no rank.
+    pub fn rank(&self, word: &str) -> Option<usize> {
+        self.ranks.get(word).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn ranks_words() {
+        let frequencies = Frequencies::load("og 900\nvera 1200\nhestur 15\n".as_bytes()).unwrap();
+        assert_eq!(Some(0), frequencies.rank("vera"));
+        assert_eq!(Some(1), frequencies.rank("og"));
+        assert_eq!(Some(2), frequencies.rank("hestur"));
+        assert_eq!(None, frequencies.rank("birta"));
+
+        let frequencies = Frequencies::load("vera\nog\n\nhestur\n".as_bytes()).unwrap();
+        assert_eq!(Some(0), frequencies.rank("vera"));
+        assert_eq!(Some(2), frequencies.rank("hestur"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Stable identifiers for generated decks and notes.
+
+use crate::ProgramError;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// The characters Anki uses to write note GUIDs.
+const GUID_ALPHABET: &[u8] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!#$%&()*+,-./:;<=>?@[]^_`{|}~";
+
+/// A 64-bit FNV-1a hash. Unlike `std::collections::hash_map::DefaultHasher`,
+/// its output is guaranteed not to change between Rust releases, so it can be
+/// used for identifiers that must stay the same from one build to the next.
+pub fn stable_hash(s: &str) -> u64 {
+    s.bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3))
+}
+
+/// Derive a deck id from the full name of the deck. Ids are kept below 2^52,
+/// as Anki's JavaScript front ends can't represent larger integers exactly.
+pub fn deck_id(name: &str) -> usize {
+    ((stable_hash(name) >> 12) | (1 << 32)) as usize
+}
+
+/// Derive the id of a note type from its name and the namespace of the deck
+/// it belongs to.
+pub fn model_id(namespace: &str, name: &str) -> usize {
+    deck_id(&format!("{}\x1f{}", namespace, name))
+}
+
+/// Derive a note GUID from the parts of the note's identity, written the
+/// same way Anki writes the GUIDs of the notes it creates.
+pub fn note_guid(parts: &[&str]) -> String {
+    let mut hash = stable_hash(&parts.join("\x1f"));
+    let base = GUID_ALPHABET.len() as u64;
+
+    let mut guid = Vec::new();
+    loop {
+        guid.push(GUID_ALPHABET[(hash % base) as usize]);
+        hash /= base;
+        if hash == 0 {
+            break;
+        }
+    }
+    guid.reverse();
+
+    String::from_utf8(guid).unwrap()
+}
+
+/// The ids of the decks and note types generated before, each with its name,
+/// kept in a file so that reusing an id for something else can be caught.
+#[derive(Debug)]
+pub struct IdRegistry {
+    path: PathBuf,
+    ids: BTreeMap<(String, usize), String>,
+}
+
+impl IdRegistry {
+    /// Read the registry file, which holds a kind, id, and name on each line,
+    /// separated by tabs. A missing file is an empty registry.
+    pub fn load(path: &Path) -> Result<Self, ProgramError> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut ids = BTreeMap::new();
+        for line in text.lines() {
+            let mut fields = line.splitn(3, '\t');
+            if let (Some(kind), Some(Ok(id)), Some(name)) =
+                (fields.next(), fields.next().map(str::parse), fields.next())
+            {
+                ids.insert((kind.to_string(), id), name.to_string());
+            }
+        }
+
+        Ok(IdRegistry { path: path.to_path_buf(), ids })
+    }
+
+    /// Record that a deck or note type uses `id`. Returns the name it was
+    /// used for before, if that was another name.
+    pub fn claim(&mut self, kind: &str, id: usize, name: &str) -> Option<String> {
+        self.ids
+            .insert((kind.to_string(), id), name.to_string())
+            .filter(|previous| previous != name)
+    }
+
+    pub fn save(&self) -> Result<(), ProgramError> {
+        let lines: String = self
+            .ids
+            .iter()
+            .map(|((kind, id), name)| format!("{}\t{}\t{}\n", kind, id, name))
+            .collect();
+        std::fs::write(&self.path, lines)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn hashes_are_stable() {
+        assert_eq!(0xcbf29ce484222325, stable_hash(""));
+        assert_eq!(0xaf63dc4c8601ec8c, stable_hash("a"));
+        assert_eq!(deck_id("Icelandic::Nouns"), deck_id("Icelandic::Nouns"));
+        assert_ne!(deck_id("Icelandic::Nouns"), deck_id("Icelandic::Verbs"));
+        assert!(deck_id("Icelandic::Nouns") < 1 << 52);
+
+        assert_eq!(note_guid(&["hestur", "noun", ""]), note_guid(&["hestur", "noun", ""]));
+        assert_ne!(note_guid(&["hestur", "noun", ""]), note_guid(&["hestur", "noun", "5634"]));
+        assert_ne!(note_guid(&["ab", "c"]), note_guid(&["a", "bc"]));
+        assert_eq!("DWWLZr=MF}", note_guid(&["a"]));
+
+        assert_ne!(model_id("Course 1", "Icelandic Noun"), model_id("Course 2", "Icelandic Noun"));
+    }
+
+    #[test]
+    pub fn registers_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ids.tsv");
+
+        let mut registry = IdRegistry::load(&path).unwrap();
+        assert_eq!(None, registry.claim("deck", 1, "Icelandic Vocabulary"));
+        assert_eq!(None, registry.claim("note type", 1, "Icelandic Noun"));
+        registry.save().unwrap();
+
+        let mut registry = IdRegistry::load(&path).unwrap();
+        assert_eq!(None, registry.claim("deck", 1, "Icelandic Vocabulary"));
+        assert_eq!(
+            Some("Icelandic Vocabulary".to_string()),
+            registry.claim("deck", 1, "Icelandic Online 2")
+        );
+        assert_eq!(None, registry.claim("deck", 1, "Icelandic Online 2"));
+    }
+}
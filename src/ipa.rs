@@ -0,0 +1,305 @@
+//! Transcription of Icelandic words into IPA by the regular rules of its
+//! spelling, such as preaspiration and "ll" as [tl], with a list of
+//! exceptions for words that don't follow them.
+
+use crate::ProgramError;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+const VOWELS: [char; 14] = ['a', 'á', 'e', 'é', 'i', 'í', 'o', 'ó', 'u', 'ú', 'y', 'ý', 'æ', 'ö'];
+
+/// Letters before which k and g are palatal.
+const FRONT: [char; 7] = ['e', 'i', 'í', 'y', 'ý', 'æ', 'j'];
+
+/// Vowels that are pronounced long or as diphthongs, after which "nn" is
+/// [tn].
+const LONG_VOWELS: [char; 7] = ['á', 'é', 'í', 'ó', 'ú', 'ý', 'æ'];
+
+/// Sounds after which a final l, m, n, or r is voiceless, as in "vatn".
+const VOICELESS: [char; 10] = ['p', 't', 'k', 'c', 's', 'f', 'θ', 'x', 'ç', 'ʰ'];
+
+/// The diacritic marking a voiceless sonorant.
+const RING_BELOW: char = '\u{325}';
+
+/// The diacritic marking a voiceless sonorant with a descender, as in [ŋ̊].
+const RING_ABOVE: char = '\u{30a}';
+
+/// Transcriptions of words that the spelling rules get wrong, such as loan
+/// words and names.
+#[derive(Debug, Default)]
+pub struct Pronunciations {
+    exceptions: HashMap<String, String>,
+}
+
+impl Pronunciations {
+    /// Read a list of exceptions. Each line holds a word and its
+    /// transcription, separated by a tab, or by spaces if the word is a
+    /// single word. The transcription may be given in brackets or slashes.
+    /// Empty lines and lines starting with `#` are skipped.
+    pub fn load<T>(reader: T) -> Result<Self, ProgramError>
+    where
+        T: Read,
+    {
+        let mut exceptions = HashMap::new();
+
+        for line in BufReader::new(reader).lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let split = match line.split_once('\t') {
+                Some(split) => Some(split),
+                None => line.split_once(char::is_whitespace),
+            };
+            if let Some((word, transcription)) = split {
+                let transcription = transcription.trim().trim_matches(['[', ']', '/']);
+                exceptions.insert(word.trim().to_lowercase(), transcription.to_string());
+            }
+        }
+
+        Ok(Pronunciations { exceptions })
+    }
+
+    /// The IPA transcription of a word or phrase, without brackets. Each
+    /// word is transcribed separately, unless the whole phrase is an
+    /// exception.
+    pub fn transcribe(&self, text: &str) -> String {
+        let text = text.trim().to_lowercase();
+        if let Some(transcription) = self.exceptions.get(&text) {
+            return transcription.clone();
+        }
+
+        text.split_whitespace()
+            .map(|word| match self.exceptions.get(word) {
+                Some(transcription) => transcription.clone(),
+                None => transcribe_word(word),
+            })
+            .filter(|transcription| !transcription.is_empty())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+/// A sound, or a cluster of sounds spelled together, of a word.
+struct Phone {
+    ipa: String,
+    vowel: bool,
+}
+
+/// Transcribe a word by the rules of Icelandic spelling. The first syllable
+/// is stressed, and its vowel is long before at most one consonant, or
+/// before p, t, k, or s followed by j, v, or r.
+fn transcribe_word(word: &str) -> String {
+    let letters: Vec<char> = word.to_lowercase().chars().filter(|c| c.is_alphabetic()).collect();
+    let mut phones: Vec<Phone> = Vec::new();
+
+    let mut i = 0;
+    while i < letters.len() {
+        let (ipa, length, vowel) = match vowel(&letters, i) {
+            Some((ipa, length)) => (ipa.to_string(), length, true),
+            None => {
+                let (ipa, length) = consonant(&letters, i);
+                (ipa, length, false)
+            }
+        };
+        phones.push(Phone { ipa, vowel });
+        i += length;
+    }
+
+    // A final sonorant after a voiceless sound is voiceless itself.
+    let ipa: String = phones.iter().map(|phone| phone.ipa.as_str()).collect();
+    if let Some(last) = phones.last_mut().filter(|phone| !phone.vowel) {
+        let mut chars = ipa.chars().rev();
+        if let (Some('l' | 'm' | 'n' | 'r'), Some(before)) = (chars.next(), chars.next()) {
+            if VOICELESS.contains(&before) {
+                last.ipa.push(RING_BELOW);
+            }
+        }
+    }
+
+    if let Some(stressed) = phones.iter().position(|phone| phone.vowel) {
+        let following: Vec<char> = phones[stressed + 1..]
+            .iter()
+            .take_while(|phone| !phone.vowel)
+            .flat_map(|phone| phone.ipa.chars())
+            .filter(|&c| c != RING_BELOW && c != RING_ABOVE)
+            .collect();
+        let long = match following.as_slice() {
+            [] | [_] => true,
+            [first, second] => {
+                ['p', 't', 'k', 's'].contains(first) && ['j', 'v', 'r'].contains(second)
+            }
+            _ => false,
+        };
+        if long {
+            phones[stressed].ipa.push('ː');
+        }
+    }
+
+    let syllables = phones.iter().filter(|phone| phone.vowel).count();
+    let ipa: String = phones.into_iter().map(|phone| phone.ipa).collect();
+    if syllables > 1 {
+        format!("ˈ{}", ipa)
+    } else {
+        ipa
+    }
+}
+
+/// The vowel or diphthong spelled at `i`, and the number of letters it's
+/// spelled with. Vowels before "ng" and "nk" are long or diphthongs.
+fn vowel(letters: &[char], i: usize) -> Option<(&'static str, usize)> {
+    let next = letters.get(i + 1).copied();
+    let before_ng = next == Some('n') && matches!(letters.get(i + 2), Some('g' | 'k'));
+
+    let vowel = match (letters[i], next) {
+        ('a', Some('u')) => ("œy", 2),
+        ('e', Some('i' | 'y')) => ("ei", 2),
+        ('a', _) if before_ng => ("au", 1),
+        ('e', _) if before_ng => ("ei", 1),
+        ('i' | 'y', _) if before_ng => ("i", 1),
+        ('o', _) if before_ng => ("ou", 1),
+        ('u', _) if before_ng => ("u", 1),
+        ('ö', _) if before_ng => ("œy", 1),
+        ('a', _) => ("a", 1),
+        ('á', _) => ("au", 1),
+        ('e', _) => ("ɛ", 1),
+        ('é', _) => ("jɛ", 1),
+        ('i' | 'y', _) => ("ɪ", 1),
+        ('í' | 'ý', _) => ("i", 1),
+        ('o', _) => ("ɔ", 1),
+        ('ó', _) => ("ou", 1),
+        ('u', _) => ("ʏ", 1),
+        ('ú', _) => ("u", 1),
+        ('ö', _) => ("œ", 1),
+        ('æ', _) => ("ai", 1),
+        _ => return None,
+    };
+
+    Some(vowel)
+}
+
+/// The consonant or cluster spelled at `i`, and the number of letters it's
+/// spelled with.
+fn consonant(letters: &[char], i: usize) -> (String, usize) {
+    let at = |i: usize| letters.get(i).copied();
+    let is = |i: usize, set: &[char]| at(i).is_some_and(|c| set.contains(&c));
+    let c = letters[i];
+    let next = at(i + 1);
+    let initial = i == 0;
+    let after_vowel = i > 0 && VOWELS.contains(&letters[i - 1]);
+    let double = next == Some(c);
+    let length = if double { 2 } else { 1 };
+
+    let (ipa, length): (String, usize) = match c {
+        'p' | 't' | 'k' => {
+            let stop = if c == 'k' && is(i + length, &FRONT) { 'c' } else { c };
+            if initial {
+                (format!("{}ʰ", stop), 1)
+            } else if after_vowel && (double || is(i + 1, &['l', 'm', 'n'])) {
+                // Preaspiration, as in "takk" and "epli".
+                (format!("ʰ{}", stop), length)
+            } else {
+                (stop.to_string(), length)
+            }
+        }
+        'b' => ("p".to_string(), length),
+        'd' => ("t".to_string(), length),
+        'g' if double => ((if is(i + 2, &FRONT) { "c" } else { "k" }).to_string(), 2),
+        'g' if initial || !after_vowel => {
+            ((if is(i + 1, &FRONT) { "c" } else { "k" }).to_string(), 1)
+        }
+        'g' => match next {
+            Some('t' | 's') => ("x".to_string(), 1),
+            Some('l' | 'n') => ("k".to_string(), 1),
+            Some('j') => ("j".to_string(), 2),
+            Some('i') => ("j".to_string(), 1),
+            _ => ("ɣ".to_string(), 1),
+        },
+        'f' if double => ("f".to_string(), 2),
+        'f' if initial || is(i + 1, &['s', 't', 'k']) => ("f".to_string(), 1),
+        // "fn" and "fl" are [pn] and [pl], as in "nafn" and "tafla".
+        'f' if after_vowel && is(i + 1, &['l', 'n']) => ("p".to_string(), 1),
+        'f' => ("v".to_string(), 1),
+        'h' => match next {
+            Some('v') => ("kv".to_string(), 2),
+            Some('j') => ("ç".to_string(), 2),
+            Some(sonorant @ ('l' | 'n' | 'r')) => (format!("{}{}", sonorant, RING_BELOW), 2),
+            _ => ("h".to_string(), 1),
+        },
+        // "ll" is [tl], as in "fjall", except before t, s, and d.
+        'l' if double && !is(i + 2, &['t', 's', 'd']) => ("tl".to_string(), 2),
+        'n' if matches!(next, Some('g' | 'k')) => {
+            let (nasal, stop) = if is(i + 2, &FRONT) { ("ɲ", "c") } else { ("ŋ", "k") };
+            let voiceless = if next == Some('k') { RING_ABOVE.to_string() } else { String::new() };
+            (format!("{}{}{}", nasal, voiceless, stop), 2)
+        }
+        // "nn" is [tn] after long vowels and diphthongs, as in "steinn".
+        'n' if double => {
+            let diphthong =
+                i >= 2 && matches!((letters[i - 2], letters[i - 1]), ('a', 'u') | ('e', 'i' | 'y'));
+            if diphthong || (i > 0 && LONG_VOWELS.contains(&letters[i - 1])) {
+                ("tn".to_string(), 2)
+            } else {
+                ("n".to_string(), 2)
+            }
+        }
+        'r' if after_vowel && is(i + 1, &['l', 'n']) => (format!("rt{}", letters[i + 1]), 2),
+        'x' => ("xs".to_string(), 1),
+        'þ' => ("θ".to_string(), 1),
+        'c' | 'q' => ("k".to_string(), 1),
+        'z' => ("s".to_string(), 1),
+        'w' => ("v".to_string(), 1),
+        _ => (c.to_string(), length),
+    };
+
+    (ipa, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn transcribes_words() {
+        let pronunciations = Pronunciations::default();
+        let words = [
+            ("hestur", "ˈhɛstʏr"),
+            ("kaka", "ˈkʰaːka"),
+            ("epli", "ˈɛʰplɪ"),
+            ("takk", "tʰaʰk"),
+            ("vatn", "vaʰtn̥"),
+            ("fjall", "fjatl̥"),
+            ("fallegur", "ˈfatlɛɣʏr"),
+            ("nafn", "napn̥"),
+            ("steinn", "steitn̥"),
+            ("karl", "kʰartl̥"),
+            ("banki", "ˈpauɲ̊cɪ"),
+            ("langur", "ˈlauŋkʏr"),
+            ("gefa", "ˈcɛːva"),
+            ("Hlíð", "l̥iːð"),
+            ("ég", "jɛːɣ"),
+            ("hvað", "kvaːð"),
+            ("sagt", "saxt"),
+            ("eyja", "ˈeiːja"),
+        ];
+
+        for (word, ipa) in words {
+            assert_eq!(ipa, pronunciations.transcribe(word), "{}", word);
+        }
+        assert_eq!("ˈkouːðʏr ˈtaːɣʏr", pronunciations.transcribe("Góður dagur"));
+    }
+
+    #[test]
+    pub fn uses_exceptions() {
+        let exceptions =
+            "# Loan words\npizza\t[ˈpʰiːtsa]\nbless /blɛs/\ngóðan daginn\tˈkouːðan ˈtaiːɪn\n";
+        let pronunciations = Pronunciations::load(exceptions.as_bytes()).unwrap();
+
+        assert_eq!("ˈpʰiːtsa", pronunciations.transcribe("Pizza"));
+        assert_eq!("blɛs ˈpʰiːtsa", pronunciations.transcribe("bless pizza"));
+        assert_eq!("ˈkouːðan ˈtaiːɪn", pronunciations.transcribe("góðan daginn"));
+        assert_eq!("ˈhɛstʏr", pronunciations.transcribe("hestur"));
+    }
+}
@@ -1,8 +1,23 @@
-use crate::bindata::{BinData, Gender};
-use crate::dictionary::{Category, Dictionary};
-use clap::{App, Arg};
+use crate::bindata::{describe_tag, is_irregular_verb, BinData, Gender, NameKind, NounEntry};
+use crate::check::check;
+use crate::corpus::Corpus;
+use crate::dictionary::{
+    collation_key, Category, ColumnMapping, Dictionary, DictionaryKey, Example, Sense,
+};
+use crate::drill::{
+    adjective_forms, cells, noun_forms, parse_cells, verb_forms, Cell, ClozeSelection, DrillKind,
+};
+use crate::frequency::Frequencies;
+use crate::ids::{deck_id, model_id, note_guid, IdRegistry};
+use crate::ipa::Pronunciations;
+use crate::manifest::{Changes, Entry, Manifest};
+use crate::media::{Media, AUDIO_EXTENSIONS, IMAGE_EXTENSIONS};
+use crate::templates::Overrides;
+use crate::tts::Tts;
+use clap::{App, AppSettings, Arg, SubCommand};
 use directories::ProjectDirs;
-use genanki_rs::{Deck, Field, Model, Note, Template};
+use genanki_rs::{Deck, Field, Model, ModelType, Note, Package, Template};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -11,11 +26,24 @@ use thiserror::Error;
 use zip::result::ZipError;
 
 mod bindata;
+mod check;
+mod corpus;
 mod dictionary;
+mod drill;
+mod frequency;
+mod ids;
+mod ipa;
+mod manifest;
+mod media;
+mod templates;
+mod tts;
 
 const DEFAULT_DECK_NAME: &str = "Icelandic Vocabulary";
 const DEFAULT_DECK_DESCRIPTION: &str = "Deck for studying Icelandic Vocabulary";
 const DEFAULT_BIN_CSV: &str = "SHsnid.csv";
+const ID_REGISTRY: &str = "ids.tsv";
+/// The most example sentences taken from the corpus for a word.
+const CORPUS_EXAMPLES: usize = 2;
 const DEFAULT_DECK: &str = "deck.apkg";
 const BIN_CSV_URL: &str = "https://bin.arnastofnun.is/django/api/nidurhal/?file=SHsnid.csv.zip";
 const NOUN_MODEL_ID: usize = 1625673414000;
@@ -26,14 +54,24 @@ const PHRASE_MODEL_ID: usize = 1625673414040;
 const PRONOUN_MODEL_ID: usize = 1625673414050;
 const INDEFINITE_PRONOUN_MODEL_ID: usize = 1625673414060;
 const NUMBER_MODEL_ID: usize = 1625673414070;
+const PROPER_NOUN_MODEL_ID: usize = 1625673414080;
+const PREPOSITION_MODEL_ID: usize = 1625673414090;
+const NOUN_DRILL_MODEL_ID: usize = 1625673414100;
+const ADJECTIVE_DRILL_MODEL_ID: usize = 1625673414110;
+const VERB_DRILL_MODEL_ID: usize = 1625673414120;
+const NOUN_TYPED_MODEL_ID: usize = 1625673414130;
+const ADJECTIVE_TYPED_MODEL_ID: usize = 1625673414140;
+const VERB_TYPED_MODEL_ID: usize = 1625673414150;
+const CLOZE_MODEL_ID: usize = 1625673414160;
 const DECK_ID: usize = 1625673415000;
 
+/// The layout of every card. Its colours come from the deck's theme.
 const CSS: &str = r#".card {
   font-family: Helvetica, Arial, sans-serif;
   font-size: 20px;
   text-align: center;
-  color: black;
-  background-color: white;
+  color: var(--text);
+  background-color: var(--background);
 }
 table {
   border-collapse: collapse;
@@ -42,7 +80,7 @@ table {
   width: 100%;
 }
 td {
-  border: 1px solid #ccc;
+  border: 1px solid var(--border);
   padding: 6px;
 }
 th {
@@ -50,20 +88,62 @@ th {
   padding: 8px;
 }
 .wclass {
-  color: #009900;
+  color: var(--accent);
   font-weight: bold;
   font-style: italic;
 }
 .definition {
-  border: 1px solid black;
+  border: 1px solid var(--definition-border);
   padding: 12px;
-  background: #eee;
+  background: var(--definition-background);
   font-size: 120%;
   font-weight: bold;
-  color: #000099;
+  color: var(--definition-text);
+}
+.senses {
+  text-align: left;
+}
+.target {
+  font-weight: bold;
+  font-style: normal;
+}
+.form-tag {
+  color: var(--muted);
+  font-size: 70%;
+  font-style: normal;
+}
+.governs {
+  color: var(--accent);
+  font-style: italic;
+  font-weight: normal;
+}
+.pronunciation {
+  margin-top: 12px;
+  color: var(--muted);
+}
+.example {
+  margin-top: 12px;
+  font-style: italic;
+}
+.translation {
+  color: var(--muted);
+  font-size: 80%;
+}
+.notes {
+  margin-top: 12px;
+  font-size: 80%;
+}
+.image img {
+  max-width: 100%;
+  margin-top: 12px;
+}
+.lesson {
+  margin-top: 12px;
+  color: var(--faint);
+  font-size: 60%;
 }
 .vp {
-  color: #999;
+  color: var(--faint);
   font-style: italic;
   font-weight: normal;
 }
@@ -96,13 +176,102 @@ th {
   width: 50%;
   font-weight: bold;
   font-size: 120%;
+}
+.form {
+  margin: 12px;
+  font-size: 150%;
+  font-weight: bold;
+}
+.gender.Masculine {
+  color: var(--masculine);
+}
+.gender.Feminine {
+  color: var(--feminine);
+}
+.gender.Neuter {
+  color: var(--neuter);
 }"#;
 
+/// The selector for cards in Anki's night mode. Older versions of Anki
+/// use `night_mode`.
+const NIGHT_MODE: &str = ".card.nightMode, .card.night_mode, .nightMode .card";
+
+const LIGHT_COLOURS: &str = r#"  --text: black;
+  --background: white;
+  --border: #ccc;
+  --accent: #009900;
+  --definition-border: black;
+  --definition-background: #eee;
+  --definition-text: #000099;
+  --muted: #666;
+  --faint: #999;
+  --masculine: #1f5fbf;
+  --feminine: #c0392b;
+  --neuter: #2e8b57;
+"#;
+
+const DARK_COLOURS: &str = r#"  --text: #ddd;
+  --background: #2f2f31;
+  --border: #555;
+  --accent: #6fcf6f;
+  --definition-border: #888;
+  --definition-background: #3a3a3c;
+  --definition-text: #9ecbff;
+  --muted: #aaa;
+  --faint: #888;
+  --masculine: #7fb2ff;
+  --feminine: #ff8a80;
+  --neuter: #7ed8a0;
+"#;
+
+const HIGH_CONTRAST_COLOURS: &str = r#"  --text: black;
+  --background: white;
+  --border: black;
+  --accent: #0000cc;
+  --definition-border: black;
+  --definition-background: white;
+  --definition-text: black;
+  --muted: black;
+  --faint: #333;
+  --masculine: #0033cc;
+  --feminine: #b00000;
+  --neuter: #006600;
+"#;
+
+const HIGH_CONTRAST_NIGHT_COLOURS: &str = r#"  --text: white;
+  --background: black;
+  --border: white;
+  --accent: #ffff00;
+  --definition-border: white;
+  --definition-background: black;
+  --definition-text: #ffff00;
+  --muted: white;
+  --faint: #ddd;
+  --masculine: #66ccff;
+  --feminine: #ff6666;
+  --neuter: #66ff66;
+"#;
+
+/// Black on white without shaded backgrounds, for printing cards out.
+const PRINT_COLOURS: &str = r#"  --text: black;
+  --background: white;
+  --border: black;
+  --accent: black;
+  --definition-border: black;
+  --definition-background: white;
+  --definition-text: black;
+  --muted: #333;
+  --faint: #555;
+  --masculine: #1f3f7f;
+  --feminine: #7f1f1f;
+  --neuter: #1f5f3f;
+"#;
+
 const NOUN_TMPL: &str = r#"{{FrontSide}}
-<p class="wclass">Noun — {{Gender}}</p>
-<p class="definition">
+<p class="wclass">Noun — <span class="gender {{Gender}}">{{Gender}}</span></p>
+<div class="definition">
  {{Definition}}
-</p>
+</div>
 <h3>Indefinite</h3>
 <table>
  <tr>
@@ -160,9 +329,97 @@ const NOUN_TMPL: &str = r#"{{FrontSide}}
  </tr>
 </table>"#;
 
+const PROPER_NOUN_TMPL: &str = r#"{{FrontSide}}
+<p class="wclass">{{Name Type}} — <span class="gender {{Gender}}">{{Gender}}</span></p>
+<div class="definition">{{Definition}}</div>
+{{#Nominative Singular}}
+<h3>Singular</h3>
+<table>
+ <tr>
+  <th class="acl">nom.</th>
+  <td class="pfm"><span class="vp">hér er</span> {{Nominative Singular}}</td>
+ </tr>
+ <tr>
+  <th class="acl">acc.</th>
+  <td class="pfm"><span class="vp">um</span> {{Accusative Singular}}</td>
+ </tr>
+ <tr>
+  <th class="acl">dat.</th>
+  <td class="pfm"><span class="vp">frá</span> {{Dative Singular}}</td>
+ </tr>
+ <tr>
+  <th class="acl">gen.</th>
+  <td class="pfm"><span class="vp">til</span> {{Genitive Singular}}</td>
+ </tr>
+</table>
+{{/Nominative Singular}}
+{{#Nominative Singular Definite}}
+<h3>Singular Definite</h3>
+<table>
+ <tr>
+  <th class="acl">nom.</th>
+  <td class="pfm"><span class="vp">hér er</span> {{Nominative Singular Definite}}</td>
+ </tr>
+ <tr>
+  <th class="acl">acc.</th>
+  <td class="pfm"><span class="vp">um</span> {{Accusative Singular Definite}}</td>
+ </tr>
+ <tr>
+  <th class="acl">dat.</th>
+  <td class="pfm"><span class="vp">frá</span> {{Dative Singular Definite}}</td>
+ </tr>
+ <tr>
+  <th class="acl">gen.</th>
+  <td class="pfm"><span class="vp">til</span> {{Genitive Singular Definite}}</td>
+ </tr>
+</table>
+{{/Nominative Singular Definite}}
+{{#Nominative Plural}}
+<h3>Plural</h3>
+<table>
+ <tr>
+  <th class="acl">nom.</th>
+  <td class="pfm"><span class="vp">hér eru</span> {{Nominative Plural}}</td>
+ </tr>
+ <tr>
+  <th class="acl">acc.</th>
+  <td class="pfm"><span class="vp">um</span> {{Accusative Plural}}</td>
+ </tr>
+ <tr>
+  <th class="acl">dat.</th>
+  <td class="pfm"><span class="vp">frá</span> {{Dative Plural}}</td>
+ </tr>
+ <tr>
+  <th class="acl">gen.</th>
+  <td class="pfm"><span class="vp">til</span> {{Genitive Plural}}</td>
+ </tr>
+</table>
+{{/Nominative Plural}}
+{{#Nominative Plural Definite}}
+<h3>Plural Definite</h3>
+<table>
+ <tr>
+  <th class="acl">nom.</th>
+  <td class="pfm"><span class="vp">hér eru</span> {{Nominative Plural Definite}}</td>
+ </tr>
+ <tr>
+  <th class="acl">acc.</th>
+  <td class="pfm"><span class="vp">um</span> {{Accusative Plural Definite}}</td>
+ </tr>
+ <tr>
+  <th class="acl">dat.</th>
+  <td class="pfm"><span class="vp">frá</span> {{Dative Plural Definite}}</td>
+ </tr>
+ <tr>
+  <th class="acl">gen.</th>
+  <td class="pfm"><span class="vp">til</span> {{Genitive Plural Definite}}</td>
+ </tr>
+</table>
+{{/Nominative Plural Definite}}"#;
+
 const NUMBER_TMPL: &str = r#"{{FrontSide}}
 <p class="wclass">Number</p>
-<p class="definition">{{Definition}}</p>
+<div class="definition">{{Definition}}</div>
 <table>
  <tr>
   <th class="acl"></th>
@@ -201,7 +458,7 @@ const NUMBER_TMPL: &str = r#"{{FrontSide}}
 // other templates.
 const ADJ_TMPL: &str = r#"{{FrontSide}}
 <p class="wclass">{{Word Class}}</p>
-<p class="definition">{{Definition}}</p>
+<div class="definition">{{Definition}}</div>
 <h3>Singular</h3>
 <table>
  <tr>
@@ -271,7 +528,7 @@ const ADJ_TMPL: &str = r#"{{FrontSide}}
 
 const VERB_TMPL: &str = r#"{{FrontSide}}
 <p class="wclass">Verb</p>
-<p class="definition">{{Definition}}</p>
+<div class="definition">{{Definition}}</div>
 <h3>Present Indicative</h3>
 <table>
  <tr>
@@ -329,15 +586,19 @@ const VERB_TMPL: &str = r#"{{FrontSide}}
 
 const ADVERB_TMPL: &str = r#"{{FrontSide}}
 <p class="wclass">Adverb</p>
-<p class="definition">{{Definition}}</p>"#;
+<div class="definition">{{Definition}}</div>"#;
 
 const PHRASE_TMPL: &str = r#"{{FrontSide}}
 <p class="wclass">Phrase</p>
-<p class="definition">{{Definition}}</p>"#;
+<div class="definition">{{Definition}}</div>"#;
+
+const PREPOSITION_TMPL: &str = r#"{{FrontSide}}
+<p class="wclass">Preposition</p>
+<div class="definition">{{Definition}}</div>"#;
 
 const PRONOUN_TMPL: &str = r#"{{FrontSide}}
 <p class="wclass">Pronoun</p>
-<p class="definition">{{Definition}}</p>
+<div class="definition">{{Definition}}</div>
 <table>
  <tr>
   <th class="acl">nom.</th>
@@ -357,12 +618,85 @@ const PRONOUN_TMPL: &str = r#"{{FrontSide}}
  </tr>
 </table>"#;
 
+/// The front of a drill card, which asks for one form of a word. The root
+/// is filled in per model.
+const DRILL_FRONT_TMPL: &str = r#"<h1>{root}</h1>
+<p class="wclass">{{Cell}}?</p>"#;
+
+const DRILL_BACK_TMPL: &str = r#"{{FrontSide}}
+<hr id="answer">
+<div class="form">{{Form}}</div>
+<div class="definition">{{Definition}}</div>"#;
+
+/// A drill card on which the form is typed in, and compared with the right
+/// one by Anki. The root is filled in per model.
+const TYPED_FRONT_TMPL: &str = r#"<h1>{root}</h1>
+<p class="wclass">{{Cell}}?</p>
+{{type:Form}}"#;
+
+const TYPED_BACK_TMPL: &str = r#"<h1>{root}</h1>
+<p class="wclass">{{Cell}}?</p>
+<hr id="answer">
+<div class="form">{{type:Form}}</div>
+<div class="definition">{{Definition}}</div>"#;
+
+/// A paradigm table with some of its cells blanked out. The table is the
+/// same on both sides of the card, with the blanks filled in on the back.
+const CLOZE_TMPL: &str = r#"<h1>{{Root}}</h1>
+<p class="wclass">{{Word Class}}</p>
+<div class="definition">{{Definition}}</div>
+{{cloze:Table}}"#;
+
+/// The front of a reverse card, which asks for the Icelandic word. The word
+/// class is filled in per model.
+const REVERSE_FRONT_TMPL: &str = r#"<p class="wclass">{word_class}</p>
+<div class="definition">{{Definition}}</div>"#;
+
+/// The back of a reverse card. The root, and the gender of nouns, are
+/// filled in per model.
+const REVERSE_BACK_TMPL: &str = r#"{{FrontSide}}
+<hr id="answer">
+<h1>{root}</h1>
+{gender}"#;
+
+/// The front of a picture card, which only notes with an image have. The
+/// word class is filled in per model.
+const PICTURE_FRONT_TMPL: &str = r#"{{#Image}}
+<p class="wclass">{word_class}</p>
+<div class="image">{{Image}}</div>
+{{/Image}}"#;
+
+/// The back of a picture card, which shows the image again below it with
+/// the other extras. The root, and the gender of nouns, are filled in per
+/// model.
+const PICTURE_BACK_TMPL: &str = r#"<h1>{root}</h1>
+{gender}
+<hr id="answer">
+<div class="definition">{{Definition}}</div>"#;
+
+/// Shown below the back of every card, for the optional wordlist columns.
+const EXTRAS_TMPL: &str = r#"{{#Pronunciation}}<div class="pronunciation">{{Pronunciation}}</div>{{/Pronunciation}}
+{{#Example}}
+<div class="example">
+ {{Example}}
+ {{#Example Translation}}<div class="translation">{{Example Translation}}</div>{{/Example Translation}}
+</div>
+{{/Example}}
+{{#Notes}}<div class="notes">{{Notes}}</div>{{/Notes}}
+{{Audio}}
+{{#Image}}<div class="image">{{Image}}</div>{{/Image}}
+{{#Lesson}}<div class="lesson">Lesson {{Lesson}}</div>{{/Lesson}}"#;
+
 #[derive(Error, Debug)]
 pub enum ProgramError {
     #[error("cannot access configuration")]
     Configuration,
-    #[error("invalid dictionary file")]
-    Dictionary,
+    #[error("invalid dictionary file: {0}")]
+    Dictionary(String),
+    #[error("invalid template: {0}")]
+    Template(String),
+    #[error("invalid media: {0}")]
+    Media(String),
     #[error("io error")]
     Io(#[from] io::Error),
     #[error("network error")]
@@ -377,448 +711,1582 @@ pub enum ProgramError {
     Anki(#[from] genanki_rs::Error),
 }
 
-fn common_fields() -> Vec<Field> {
+/// The paradigm cells whose forms are transcribed along with the root with
+/// `--ipa forms`: the genitive singular and nominative plural of nouns, the
+/// feminine and neuter of adjectives, and the present and past of verbs.
+const PRINCIPAL_PARTS: [&str; 6] =
+    ["gen-sg", "nom-pl", "fem-nom-sg", "neut-nom-sg", "pres-3sg", "past-1sg"];
+
+fn common_fields() -> Vec<&'static str> {
+    vec![
+        "Root",
+        "Definition",
+        "Word Class",
+        "Masculine Singular Nominative",
+        "Feminine Singular Nominative",
+        "Neuter Singular Nominative",
+        "Masculine Singular Accusative",
+        "Feminine Singular Accusative",
+        "Neuter Singular Accusative",
+        "Masculine Singular Dative",
+        "Feminine Singular Dative",
+        "Neuter Singular Dative",
+        "Masculine Singular Genitive",
+        "Feminine Singular Genitive",
+        "Neuter Singular Genitive",
+        "Masculine Plural Nominative",
+        "Feminine Plural Nominative",
+        "Neuter Plural Nominative",
+        "Masculine Plural Accusative",
+        "Feminine Plural Accusative",
+        "Neuter Plural Accusative",
+        "Masculine Plural Dative",
+        "Feminine Plural Dative",
+        "Neuter Plural Dative",
+        "Masculine Plural Genitive",
+        "Feminine Plural Genitive",
+        "Neuter Plural Genitive",
+    ]
+}
+
+/// Fields for the optional wordlist columns, which every model carries after
+/// its own fields.
+fn extra_fields() -> Vec<&'static str> {
     vec![
-        Field::new("Root"),
-        Field::new("Definition"),
-        Field::new("Word Class"),
-        Field::new("Masculine Singular Nominative"),
-        Field::new("Feminine Singular Nominative"),
-        Field::new("Neuter Singular Nominative"),
-        Field::new("Masculine Singular Accusative"),
-        Field::new("Feminine Singular Accusative"),
-        Field::new("Neuter Singular Accusative"),
-        Field::new("Masculine Singular Dative"),
-        Field::new("Feminine Singular Dative"),
-        Field::new("Neuter Singular Dative"),
-        Field::new("Masculine Singular Genitive"),
-        Field::new("Feminine Singular Genitive"),
-        Field::new("Neuter Singular Genitive"),
-        Field::new("Masculine Plural Nominative"),
-        Field::new("Feminine Plural Nominative"),
-        Field::new("Neuter Plural Nominative"),
-        Field::new("Masculine Plural Accusative"),
-        Field::new("Feminine Plural Accusative"),
-        Field::new("Neuter Plural Accusative"),
-        Field::new("Masculine Plural Dative"),
-        Field::new("Feminine Plural Dative"),
-        Field::new("Neuter Plural Dative"),
-        Field::new("Masculine Plural Genitive"),
-        Field::new("Feminine Plural Genitive"),
-        Field::new("Neuter Plural Genitive"),
+        "Example",
+        "Example Translation",
+        "Notes",
+        "Audio",
+        "Image",
+        "Lesson",
+        "Sequence",
+        "Pronunciation",
     ]
 }
 
-fn generate_deck(
+/// The index of the Sequence field among a note's fields, which end with
+/// the extra fields.
+fn sequence_field(fields: &[String]) -> usize {
+    let extra = extra_fields();
+    let position = extra.iter().position(|&name| name == "Sequence").unwrap_or_default();
+    fields.len() - extra.len() + position
+}
+
+/// Builds the note types of a deck. Each deck has note types of its own,
+/// with ids derived from its model namespace, so that customising the note
+/// types of one deck doesn't change another's. Without a namespace, the
+/// note types keep the ids they have always had.
+struct Models<'a> {
+    namespace: Option<&'a str>,
+    /// The categories whose note types also have a reverse card.
+    reverse: &'a [Category],
+    /// The categories whose note types also have a picture card.
+    pictures: &'a [Category],
+    /// User overrides of the built-in templates and CSS.
+    overrides: Option<&'a Overrides>,
+    /// The built-in CSS, with the colours of the deck's theme.
+    css: String,
+    /// The ids and names of the note types built so far.
+    built: Vec<(usize, String)>,
+}
+
+impl<'a> Models<'a> {
+    fn new(
+        namespace: Option<&'a str>,
+        reverse: &'a [Category],
+        pictures: &'a [Category],
+        overrides: Option<&'a Overrides>,
+        theme: &Theme,
+    ) -> Self {
+        let css = format!("{}\n{}", theme.css(), CSS);
+        Models { namespace, reverse, pictures, overrides, css, built: Vec::new() }
+    }
+
+    fn model(
+        &mut self,
+        legacy_id: usize,
+        category: Option<Category>,
+        name: &str,
+        fields: Vec<&str>,
+        front: &str,
+        back: &str,
+    ) -> Result<Model, ProgramError> {
+        let (id, full_name) = self.identify(legacy_id, name);
+        let fields = [fields, extra_fields()].concat();
+
+        // The reverse and picture cards come after the first, so that adding
+        // them doesn't change the existing cards of notes imported before.
+        let (front, back) = self.card(name, &fields, "", front, back)?;
+        let mut templates = vec![Template::new(&full_name).qfmt(&front).afmt(&back)];
+        if let Some(category) = category.as_ref().filter(|c| self.reverse.contains(c)) {
+            let (reverse_front, reverse_back) =
+                category_templates(category, REVERSE_FRONT_TMPL, REVERSE_BACK_TMPL);
+            let (front, back) =
+                self.card(name, &fields, "reverse-", &reverse_front, &reverse_back)?;
+            templates.push(Template::new(&format!("{} (reverse)", name)).qfmt(&front).afmt(&back));
+        }
+        if let Some(category) = category.as_ref().filter(|c| self.pictures.contains(c)) {
+            let (picture_front, picture_back) =
+                category_templates(category, PICTURE_FRONT_TMPL, PICTURE_BACK_TMPL);
+            let (front, back) =
+                self.card(name, &fields, "picture-", &picture_front, &picture_back)?;
+            templates.push(Template::new(&format!("{} (picture)", name)).qfmt(&front).afmt(&back));
+        }
+
+        self.build(id, &full_name, name, &fields, templates, ModelType::FrontBack)
+    }
+
+    /// A cloze note type, which makes a card for each cloze number used in
+    /// the fields of a note.
+    fn cloze(
+        &mut self,
+        legacy_id: usize,
+        name: &str,
+        fields: Vec<&str>,
+        front: &str,
+        back: &str,
+    ) -> Result<Model, ProgramError> {
+        let (id, full_name) = self.identify(legacy_id, name);
+        let fields = [fields, extra_fields()].concat();
+
+        let (front, back) = self.card(name, &fields, "", front, back)?;
+        let templates = vec![Template::new(&full_name).qfmt(&front).afmt(&back)];
+
+        self.build(id, &full_name, name, &fields, templates, ModelType::Cloze)
+    }
+
+    /// The front and back templates of a card. The built-in back is followed
+    /// by the optional wordlist columns. An override in the template
+    /// directory, named by `prefix` followed by "front.html" or
+    /// "back.html", replaces either side as it is.
+    fn card(
+        &self,
+        name: &str,
+        fields: &[&str],
+        prefix: &str,
+        front: &str,
+        back: &str,
+    ) -> Result<(String, String), ProgramError> {
+        let mut card = (front.to_string(), format!("{}\n{}", back, EXTRAS_TMPL));
+
+        if let Some(overrides) = self.overrides {
+            if let Some(front) =
+                overrides.template(name, &format!("{}front.html", prefix), fields)?
+            {
+                card.0 = front;
+            }
+            if let Some(back) = overrides.template(name, &format!("{}back.html", prefix), fields)? {
+                card.1 = back;
+            }
+        }
+
+        Ok(card)
+    }
+
+    fn build(
+        &self,
+        id: usize,
+        full_name: &str,
+        name: &str,
+        fields: &[&str],
+        templates: Vec<Template>,
+        model_type: ModelType,
+    ) -> Result<Model, ProgramError> {
+        let css = match self.overrides {
+            Some(overrides) => overrides.css(name)?,
+            None => None,
+        };
+
+        Ok(Model::new_with_options(
+            id,
+            full_name,
+            fields.iter().map(|field| Field::new(field)).collect(),
+            templates,
+            Some(css.as_deref().unwrap_or(&self.css)),
+            Some(model_type),
+            None,
+            None,
+            None,
+        ))
+    }
+
+    /// The id and full name of a note type, which is recorded as built.
+    fn identify(&mut self, legacy_id: usize, name: &str) -> (usize, String) {
+        let (id, full_name) = match self.namespace {
+            Some(namespace) => (model_id(namespace, name), format!("{} ({})", name, namespace)),
+            None => (legacy_id, name.to_string()),
+        };
+        self.built.push((id, full_name.clone()));
+        (id, full_name)
+    }
+}
+
+/// The front and back of a card of a category's note type that asks for
+/// the Icelandic word, such as the reverse card, which shows the definition
+/// and word class. The word class, the root, and the gender of nouns are
+/// filled into the templates.
+fn category_templates(category: &Category, front: &str, back: &str) -> (String, String) {
+    let word_class = match category {
+        Category::Noun => "Noun",
+        Category::ProperNoun => "{{Name Type}}",
+        Category::Adjective | Category::IndefinitePronoun => "{{Word Class}}",
+        Category::Verb => "Verb",
+        Category::Adverb => "Adverb",
+        Category::Phrase => "Phrase",
+        Category::Pronoun => "Pronoun",
+        Category::Number => "Number",
+        Category::Preposition => "Preposition",
+    };
+    let root = match category {
+        Category::Verb => "að {{Root}}",
+        _ => "{{Root}}",
+    };
+    let gender = match category {
+        Category::Noun | Category::ProperNoun => {
+            r#"<p class="wclass gender {{Gender}}">{{Gender}}</p>"#
+        }
+        _ => "",
+    };
+
+    let front = front.replace("{word_class}", word_class);
+    let back = back.replace("{root}", root).replace("{gender}", gender);
+    (front, back)
+}
+
+fn generate_deck<'a>(
     dictionary: &Dictionary,
     bin_data: &BinData,
     config: &AppConfig,
-) -> Result<Deck, ProgramError> {
-    let mut deck = Deck::new(DECK_ID, &config.deck_name, &config.deck_description);
+    registry: &mut IdRegistry,
+    media: &mut Media,
+    previous: Option<&'a Manifest>,
+) -> Result<Output<'a>, ProgramError> {
+    let mut output = Output::new(config, previous);
+    claim_id(registry, "deck", config.deck_id, &config.deck_name);
+
+    let overrides = config.templates.as_deref().map(Overrides::new);
+    let mut models = Models::new(
+        config.model_namespace.as_deref(),
+        &config.reverse,
+        &config.pictures,
+        overrides.as_ref(),
+        &config.theme,
+    );
 
-    let adjective_model = Model::new_with_options(
+    let adjective_model = models.model(
         ADJECTIVE_MODEL_ID,
+        Some(Category::Adjective),
         "Icelandic Adjective",
         common_fields(),
-        vec![Template::new("Icelandic Adjective").qfmt("<h1>{{Root}}</h1>").afmt(ADJ_TMPL)],
-        Some(CSS),
-        None,
-        None,
-        None,
-        None,
-    );
+        "<h1>{{Root}}</h1>",
+        ADJ_TMPL,
+    )?;
 
-    let number_model = Model::new_with_options(
+    let number_model = models.model(
         NUMBER_MODEL_ID,
+        Some(Category::Number),
         "Icelandic Numeral",
         vec![
-            Field::new("Root"),
-            Field::new("Definition"),
-            Field::new("Masculine Nominative"),
-            Field::new("Masculine Accusative"),
-            Field::new("Masculine Dative"),
-            Field::new("Masculine Genitive"),
-            Field::new("Feminine Nominative"),
-            Field::new("Feminine Accusative"),
-            Field::new("Feminine Dative"),
-            Field::new("Feminine Genitive"),
-            Field::new("Neuter Nominative"),
-            Field::new("Neuter Accusative"),
-            Field::new("Neuter Dative"),
-            Field::new("Neuter Genitive"),
+            "Root",
+            "Definition",
+            "Masculine Nominative",
+            "Masculine Accusative",
+            "Masculine Dative",
+            "Masculine Genitive",
+            "Feminine Nominative",
+            "Feminine Accusative",
+            "Feminine Dative",
+            "Feminine Genitive",
+            "Neuter Nominative",
+            "Neuter Accusative",
+            "Neuter Dative",
+            "Neuter Genitive",
         ],
-        vec![Template::new("Icelandic Numeral").qfmt("<h1>{{Root}}</h1>").afmt(NUMBER_TMPL)],
-        Some(CSS),
-        None,
-        None,
-        None,
-        None,
-    );
+        "<h1>{{Root}}</h1>",
+        NUMBER_TMPL,
+    )?;
 
-    let indef_pronoun_model = Model::new_with_options(
+    let indef_pronoun_model = models.model(
         INDEFINITE_PRONOUN_MODEL_ID,
+        Some(Category::IndefinitePronoun),
         "Icelandic Indefinite Pronoun",
         common_fields(),
-        vec![Template::new("Icelandic Indefinite Pronoun")
-            .qfmt("<h1>{{Root}}</h1>")
-            .afmt(ADJ_TMPL)],
-        Some(CSS),
-        None,
-        None,
-        None,
-        None,
-    );
+        "<h1>{{Root}}</h1>",
+        ADJ_TMPL,
+    )?;
 
-    let noun_model = Model::new_with_options(
+    let noun_model = models.model(
         NOUN_MODEL_ID,
+        Some(Category::Noun),
         "Icelandic Noun",
         vec![
-            Field::new("Root"),
-            Field::new("Definition"),
-            Field::new("Gender"),
-            Field::new("Nominative Singular"),
-            Field::new("Nominative Singular Definite"),
-            Field::new("Accusative Singular"),
-            Field::new("Accusative Singular Definite"),
-            Field::new("Dative Singular"),
-            Field::new("Dative Singular Definite"),
-            Field::new("Genitive Singular"),
-            Field::new("Genitive Singular Definite"),
-            Field::new("Nominative Plural"),
-            Field::new("Nominative Plural Definite"),
-            Field::new("Accusative Plural"),
-            Field::new("Accusative Plural Definite"),
-            Field::new("Dative Plural"),
-            Field::new("Dative Plural Definite"),
-            Field::new("Genitive Plural"),
-            Field::new("Genitive Plural Definite"),
+            "Root",
+            "Definition",
+            "Gender",
+            "Nominative Singular",
+            "Nominative Singular Definite",
+            "Accusative Singular",
+            "Accusative Singular Definite",
+            "Dative Singular",
+            "Dative Singular Definite",
+            "Genitive Singular",
+            "Genitive Singular Definite",
+            "Nominative Plural",
+            "Nominative Plural Definite",
+            "Accusative Plural",
+            "Accusative Plural Definite",
+            "Dative Plural",
+            "Dative Plural Definite",
+            "Genitive Plural",
+            "Genitive Plural Definite",
         ],
-        vec![Template::new("Icelandic Noun").qfmt("<h1>{{Root}}</h1>").afmt(NOUN_TMPL)],
-        Some(CSS),
-        None,
-        None,
-        None,
-        None,
-    );
+        "<h1>{{Root}}</h1>",
+        NOUN_TMPL,
+    )?;
+
+    let proper_noun_model = models.model(
+        PROPER_NOUN_MODEL_ID,
+        Some(Category::ProperNoun),
+        "Icelandic Proper Noun",
+        vec![
+            "Root",
+            "Definition",
+            "Name Type",
+            "Gender",
+            "Nominative Singular",
+            "Nominative Singular Definite",
+            "Accusative Singular",
+            "Accusative Singular Definite",
+            "Dative Singular",
+            "Dative Singular Definite",
+            "Genitive Singular",
+            "Genitive Singular Definite",
+            "Nominative Plural",
+            "Nominative Plural Definite",
+            "Accusative Plural",
+            "Accusative Plural Definite",
+            "Dative Plural",
+            "Dative Plural Definite",
+            "Genitive Plural",
+            "Genitive Plural Definite",
+        ],
+        "<h1>{{Root}}</h1>",
+        PROPER_NOUN_TMPL,
+    )?;
 
-    let verb_model = Model::new_with_options(
+    let verb_model = models.model(
         VERB_MODEL_ID,
+        Some(Category::Verb),
         "Icelandic Verb",
         vec![
-            Field::new("Root"),
-            Field::new("Definition"),
-            Field::new("Present 1st Singular"),
-            Field::new("Present 2nd Singular"),
-            Field::new("Present 3rd Singular"),
-            Field::new("Present 1st Plural"),
-            Field::new("Present 2nd Plural"),
-            Field::new("Present 3rd Plural"),
-            Field::new("Past 1st Singular"),
-            Field::new("Past 2nd Singular"),
-            Field::new("Past 3rd Singular"),
-            Field::new("Past 1st Plural"),
-            Field::new("Past 2nd Plural"),
-            Field::new("Past 3rd Plural"),
+            "Root",
+            "Definition",
+            "Present 1st Singular",
+            "Present 2nd Singular",
+            "Present 3rd Singular",
+            "Present 1st Plural",
+            "Present 2nd Plural",
+            "Present 3rd Plural",
+            "Past 1st Singular",
+            "Past 2nd Singular",
+            "Past 3rd Singular",
+            "Past 1st Plural",
+            "Past 2nd Plural",
+            "Past 3rd Plural",
         ],
-        vec![Template::new("Icelandic Verb").qfmt("<h1>að {{Root}}</h1>").afmt(VERB_TMPL)],
-        Some(CSS),
-        None,
-        None,
-        None,
-        None,
-    );
+        "<h1>að {{Root}}</h1>",
+        VERB_TMPL,
+    )?;
 
-    let adverb_model = Model::new_with_options(
+    let adverb_model = models.model(
         ADVERB_MODEL_ID,
+        Some(Category::Adverb),
         "Icelandic Adverb",
-        vec![Field::new("Root"), Field::new("Definition")],
-        vec![Template::new("Icelandic Adverb").qfmt("<h1>{{Root}}</h1>").afmt(ADVERB_TMPL)],
-        Some(CSS),
-        None,
-        None,
-        None,
-        None,
-    );
+        vec!["Root", "Definition"],
+        "<h1>{{Root}}</h1>",
+        ADVERB_TMPL,
+    )?;
 
-    let phrase_model = Model::new_with_options(
+    let phrase_model = models.model(
         PHRASE_MODEL_ID,
+        Some(Category::Phrase),
         "Icelandic Phrase",
-        vec![Field::new("Root"), Field::new("Definition")],
-        vec![Template::new("Icelandic Phrase").qfmt("<h1>{{Root}}</h1>").afmt(PHRASE_TMPL)],
-        Some(CSS),
-        None,
-        None,
-        None,
-        None,
-    );
+        vec!["Root", "Definition"],
+        "<h1>{{Root}}</h1>",
+        PHRASE_TMPL,
+    )?;
+
+    let preposition_model = models.model(
+        PREPOSITION_MODEL_ID,
+        Some(Category::Preposition),
+        "Icelandic Preposition",
+        vec!["Root", "Definition"],
+        "<h1>{{Root}}</h1>",
+        PREPOSITION_TMPL,
+    )?;
 
-    let pronoun_model = Model::new_with_options(
+    let pronoun_model = models.model(
         PRONOUN_MODEL_ID,
+        Some(Category::Pronoun),
         "Icelandic Pronoun",
-        vec![
-            Field::new("Root"),
-            Field::new("Definition"),
-            Field::new("Nominative"),
-            Field::new("Accusative"),
-            Field::new("Dative"),
-            Field::new("Genitive"),
-        ],
-        vec![Template::new("Icelandic Pronoun").qfmt("<h1>{{Root}}</h1>").afmt(PRONOUN_TMPL)],
-        Some(CSS),
-        None,
-        None,
-        None,
-        None,
-    );
+        vec!["Root", "Definition", "Nominative", "Accusative", "Dative", "Genitive"],
+        "<h1>{{Root}}</h1>",
+        PRONOUN_TMPL,
+    )?;
+
+    // Drill note types are only made for the kinds of cells chosen.
+    let mut drill_models = HashMap::new();
+    for kind in DrillKind::ALL.iter().filter(|kind| config.drill.iter().any(|c| c.kind == **kind)) {
+        let (legacy_id, name, root) = match (kind, &config.answers) {
+            (DrillKind::Noun, AnswerMode::Flip) => {
+                (NOUN_DRILL_MODEL_ID, "Icelandic Noun Drill", "{{Root}}")
+            }
+            (DrillKind::Adjective, AnswerMode::Flip) => {
+                (ADJECTIVE_DRILL_MODEL_ID, "Icelandic Adjective Drill", "{{Root}}")
+            }
+            (DrillKind::Verb, AnswerMode::Flip) => {
+                (VERB_DRILL_MODEL_ID, "Icelandic Verb Drill", "að {{Root}}")
+            }
+            (DrillKind::Noun, AnswerMode::Typed) => {
+                (NOUN_TYPED_MODEL_ID, "Icelandic Noun Typed Drill", "{{Root}}")
+            }
+            (DrillKind::Adjective, AnswerMode::Typed) => {
+                (ADJECTIVE_TYPED_MODEL_ID, "Icelandic Adjective Typed Drill", "{{Root}}")
+            }
+            (DrillKind::Verb, AnswerMode::Typed) => {
+                (VERB_TYPED_MODEL_ID, "Icelandic Verb Typed Drill", "að {{Root}}")
+            }
+        };
+        let (front, back) = match config.answers {
+            AnswerMode::Flip => (DRILL_FRONT_TMPL, DRILL_BACK_TMPL),
+            AnswerMode::Typed => (TYPED_FRONT_TMPL, TYPED_BACK_TMPL),
+        };
+
+        let model = models.model(
+            legacy_id,
+            None,
+            name,
+            vec!["Root", "Definition", "Cell", "Form"],
+            &front.replace("{root}", root),
+            &back.replace("{root}", root),
+        )?;
+        drill_models.insert(*kind, model);
+    }
+
+    // The cloze note type is only made if cloze cards are.
+    let cloze_model = config
+        .cloze
+        .as_ref()
+        .map(|_| {
+            models.cloze(
+                CLOZE_MODEL_ID,
+                "Icelandic Paradigm Cloze",
+                vec!["Root", "Definition", "Word Class", "Table"],
+                CLOZE_TMPL,
+                CLOZE_TMPL,
+            )
+        })
+        .transpose()?;
 
-    for (key, definition) in &dictionary.entries {
+    let pronunciations = match &config.ipa_exceptions {
+        Some(path) => Pronunciations::load(File::open(path)?)?,
+        None => Pronunciations::default(),
+    };
+
+    let corpus = match &config.corpus {
+        Some(path) => Some(Corpus::load(File::open(path)?)?),
+        None => None,
+    };
+
+    // Notes are numbered in deck order, so that they can be put back into
+    // that order in Anki if they've been repositioned.
+    let mut sequence = 0;
+
+    for (key, senses) in &dictionary.entries {
         let root = &key.root;
+        let pronunciation = pronunciation(key, bin_data, &pronunciations, config);
+        let found = corpus_examples(key, bin_data, corpus.as_ref());
 
-        let note = match key.category {
-            Category::Noun => noun(root, bin_data, definition, &noun_model),
-            Category::Adjective => adjective(root, bin_data, definition, &adjective_model),
-            Category::Verb => verb(root, bin_data, definition, &verb_model),
-            Category::Adverb => simple_note(root, definition, &adverb_model),
-            Category::Phrase => simple_note(root, definition, &phrase_model),
-            Category::Pronoun => pronoun(root, bin_data, definition, &pronoun_model),
-            Category::IndefinitePronoun => {
-                indefinite_pronoun(root, bin_data, definition, &indef_pronoun_model)
-            }
-            Category::Number => number(root, bin_data, definition, &number_model),
+        let groups: Vec<&[Sense]> = match config.senses {
+            SenseMode::Merge => vec![senses],
+            SenseMode::Split => senses.chunks(1).collect(),
         };
 
-        match note {
-            Some(note) => deck.add_note(note),
-            None => println!(
-                "WARNING: No entry found for root {}, category {:?}. Skipping.",
-                &root, key.category
-            ),
+        // Notes are identified across regenerations of the deck by their
+        // identity rather than their contents. If several notes of a word
+        // share an identity, the later ones are told apart by their position.
+        let identities: Vec<String> =
+            groups.iter().map(|group| note_identity(key, group)).collect();
+
+        for (index, group) in groups.into_iter().enumerate() {
+            let identity = &identities[index];
+            let guid = if identities[..index].contains(identity) {
+                deck_note_guid(config, &[identity, &index.to_string()])
+            } else {
+                deck_note_guid(config, &[identity])
+            };
+
+            let definition = &definition_html(group);
+
+            let (model, fields) = match key.category {
+                Category::Noun => (&noun_model, noun(root, key.bin_id, bin_data, definition)),
+                Category::ProperNoun => {
+                    (&proper_noun_model, proper_noun(root, key.bin_id, bin_data, definition))
+                }
+                Category::Adjective => {
+                    (&adjective_model, adjective(root, key.bin_id, bin_data, definition))
+                }
+                Category::Verb => (&verb_model, verb(root, key.bin_id, bin_data, definition)),
+                Category::Adverb => (&adverb_model, simple_note(root, definition)),
+                Category::Phrase => (&phrase_model, simple_note(root, definition)),
+                Category::Preposition => (&preposition_model, simple_note(root, definition)),
+                Category::Pronoun => (&pronoun_model, pronoun(root, bin_data, definition)),
+                Category::IndefinitePronoun => (
+                    &indef_pronoun_model,
+                    indefinite_pronoun(root, key.bin_id, bin_data, definition),
+                ),
+                Category::Number => (&number_model, number(root, key.bin_id, bin_data, definition)),
+            };
+
+            match fields {
+                Some(mut fields) => {
+                    sequence += 1;
+                    let audio = word_audio(key, group, media);
+                    let image = word_image(key, group, media);
+                    let files: Vec<String> = audio.iter().chain(&image).cloned().collect();
+                    fields.extend(extra_values(group, &found, audio, image));
+                    fields.push(format!("{:05}", sequence));
+                    fields.push(pronunciation.clone());
+
+                    let tags = note_tags(key, group, bin_data, config);
+                    let deck_name = deck_name(key, group, bin_data, config);
+                    let label = match identities.len() {
+                        1 => note_label(key),
+                        _ => format!("{}, sense {}", note_label(key), index + 1),
+                    };
+
+                    let note = Note::new_with_options(
+                        model.clone(),
+                        fields.iter().map(String::as_str).collect(),
+                        None,
+                        Some(tags.iter().map(String::as_str).collect()),
+                        Some(&guid),
+                    )?;
+                    let entry = Entry::new(
+                        &guid,
+                        &deck_name,
+                        &label,
+                        &fields,
+                        sequence_field(&fields),
+                        &tags,
+                    );
+                    output.add(&deck_name, note, entry, files, registry, config);
+                }
+                None => {
+                    println!(
+                        "WARNING: No entry found for root {}, category {:?}. Skipping.",
+                        &root, key.category
+                    );
+                    break;
+                }
+            }
+        }
+
+        // Drill cards are made once for each word, however its senses are
+        // split, with one note for each of the chosen cells that it has.
+        let drills = drill_forms(key, bin_data, &config.drill);
+        if !drills.is_empty() {
+            let identity = note_identity(key, senses);
+            let definition = definition_html(senses);
+            let tags = note_tags(key, senses, bin_data, config);
+            let deck_name = deck_name(key, senses, bin_data, config);
+            let audio = word_audio(key, senses, media);
+            let image = word_image(key, senses, media);
+
+            for (cell, form) in drills {
+                sequence += 1;
+
+                // With `--tts-forms`, drill cards speak the form asked for.
+                let audio = if config.tts_forms {
+                    speak(&form, media).or_else(|| audio.clone())
+                } else {
+                    audio.clone()
+                };
+
+                // Drill cards show the pronunciation of the form asked for.
+                let pronunciation = match config.ipa {
+                    IpaMode::None => String::new(),
+                    _ => format!("[{}]", pronunciations.transcribe(&form)),
+                };
+
+                let mut fields = vec![root.clone(), definition.clone(), cell.label.clone(), form];
+                let files: Vec<String> = audio.iter().chain(&image).cloned().collect();
+                fields.extend(extra_values(senses, &found, audio, image.clone()));
+                fields.push(format!("{:05}", sequence));
+                fields.push(pronunciation);
+
+                let mut tags = tags.clone();
+                tags.push(format!("drill::{}", cell.name));
+
+                let guid = deck_note_guid(config, &[&identity, config.answers.name(), &cell.name]);
+                let label = format!("{}, {}", note_label(key), cell.label);
+                let note = Note::new_with_options(
+                    drill_models[&cell.kind].clone(),
+                    fields.iter().map(String::as_str).collect(),
+                    None,
+                    Some(tags.iter().map(String::as_str).collect()),
+                    Some(&guid),
+                )?;
+                let entry =
+                    Entry::new(&guid, &deck_name, &label, &fields, sequence_field(&fields), &tags);
+                output.add(&deck_name, note, entry, files, registry, config);
+            }
+        }
+
+        // A cloze note shows the word's paradigm table, with the chosen
+        // cells blanked out.
+        if let (Some(selection), Some(cloze_model)) = (&config.cloze, &cloze_model) {
+            if let Some(fields) = cloze_fields(key, senses, bin_data, selection) {
+                sequence += 1;
+                let mut fields = fields;
+                let audio = word_audio(key, senses, media);
+                let image = word_image(key, senses, media);
+                let files: Vec<String> = audio.iter().chain(&image).cloned().collect();
+                fields.extend(extra_values(senses, &found, audio, image));
+                fields.push(format!("{:05}", sequence));
+                fields.push(pronunciation);
+
+                let mut tags = note_tags(key, senses, bin_data, config);
+                tags.push("cloze".to_string());
+
+                let identity = note_identity(key, senses);
+                let deck_name = deck_name(key, senses, bin_data, config);
+                let guid = deck_note_guid(config, &[&identity, "cloze"]);
+                let label = format!("{}, cloze", note_label(key));
+                let note = Note::new_with_options(
+                    cloze_model.clone(),
+                    fields.iter().map(String::as_str).collect(),
+                    None,
+                    Some(tags.iter().map(String::as_str).collect()),
+                    Some(&guid),
+                )?;
+                let entry =
+                    Entry::new(&guid, &deck_name, &label, &fields, sequence_field(&fields), &tags);
+                output.add(&deck_name, note, entry, files, registry, config);
+            }
         }
     }
 
-    Ok(deck)
-}
-
-fn number(root: &str, bin_data: &BinData, definition: &str, model: &Model) -> Option<Note> {
-    match bin_data.number(root) {
-        Some(entry) => Some(
-            Note::new(
-                model.clone(),
-                vec![
-                    root,
-                    definition,
-                    &entry.masc_nom.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_acc.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_dat.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_gen.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_nom.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_acc.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_dat.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_gen.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_nom.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_acc.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_dat.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_gen.unwrap_or_else(|| "—".to_string()),
-                ],
-            )
-            .unwrap(),
-        ),
-        _ => None,
+    for (id, name) in &models.built {
+        claim_id(registry, "note type", *id, name);
     }
+
+    Ok(output)
 }
 
-fn indefinite_pronoun(
-    root: &str,
-    bin_data: &BinData,
-    definition: &str,
-    model: &Model,
-) -> Option<Note> {
-    match bin_data.indefinite_pronoun(root) {
-        Some(entry) => Some(
-            Note::new(
-                model.clone(),
-                vec![
-                    root,
-                    definition,
-                    "Indefinite Pronoun",
-                    &entry.masc_nom_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_acc_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_dat_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_gen_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_nom_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_acc_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_dat_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_gen_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_nom_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_acc_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_dat_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_gen_sg.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_nom_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_acc_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_dat_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.masc_gen_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_nom_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_acc_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_dat_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.fem_gen_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_nom_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_acc_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_dat_pl.unwrap_or_else(|| "—".to_string()),
-                    &entry.neut_gen_pl.unwrap_or_else(|| "—".to_string()),
-                ],
-            )
-            .unwrap(),
-        ),
-        _ => None,
-    }
+/// The decks a build writes. Every note goes into the full decks, and is
+/// recorded in the build's manifest. With `--delta`, the notes that are new
+/// or have changed since the previous build also go into decks of their
+/// own.
+struct Output<'a> {
+    decks: BTreeMap<String, Deck>,
+    delta: Option<BTreeMap<String, Deck>>,
+    /// The names of the media files the notes of the delta use.
+    delta_files: HashSet<String>,
+    /// The manifest of the previous build, if there was one.
+    previous: Option<&'a Manifest>,
+    manifest: Manifest,
 }
 
-fn adjective(root: &str, bin_data: &BinData, definition: &str, model: &Model) -> Option<Note> {
-    match bin_data.adjective(root) {
-        Some(adjective_entry) => Some(
-            Note::new(
-                model.clone(),
-                vec![
-                    root,
-                    definition,
-                    "Adjective",
-                    &adjective_entry.masc_nom_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.fem_nom_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.neut_nom_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.masc_acc_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.fem_acc_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.neut_acc_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.masc_dat_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.fem_dat_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.neut_dat_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.masc_gen_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.fem_gen_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.neut_gen_sg_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.masc_nom_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.fem_nom_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.neut_nom_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.masc_acc_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.fem_acc_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.neut_acc_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.masc_dat_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.fem_dat_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.neut_dat_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.masc_gen_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.fem_gen_pl_strong.unwrap_or_else(|| "—".to_string()),
-                    &adjective_entry.neut_gen_pl_strong.unwrap_or_else(|| "—".to_string()),
-                ],
-            )
-            .unwrap(),
-        ),
-        _ => None,
+impl<'a> Output<'a> {
+    fn new(config: &AppConfig, previous: Option<&'a Manifest>) -> Self {
+        let mut decks = BTreeMap::new();
+        decks.insert(
+            config.deck_name.clone(),
+            Deck::new(config.deck_id, &config.deck_name, &config.deck_description),
+        );
+        let delta = config.delta.as_ref().map(|_| BTreeMap::new());
+
+        Output {
+            decks,
+            delta,
+            delta_files: HashSet::new(),
+            previous,
+            manifest: Manifest::default(),
+        }
     }
-}
 
-fn noun(root: &str, bin_data: &BinData, definition: &str, model: &Model) -> Option<Note> {
-    match bin_data.noun(root) {
-        Some(noun_entry) => Some(
-            Note::new(
-                model.clone(),
-                vec![
-                    root,
-                    definition,
-                    match noun_entry.gender {
-                        Gender::Masculine => "Masculine",
-                        Gender::Feminine => "Feminine",
-                        Gender::Neuter => "Neuter",
-                    },
-                    &noun_entry.nom_sg.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.nom_sg_def.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.acc_sg.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.acc_sg_def.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.dat_sg.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.dat_sg_def.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.gen_sg.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.gen_sg_def.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.nom_pl.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.nom_pl_def.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.acc_pl.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.acc_pl_def.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.dat_pl.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.dat_pl_def.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.gen_pl.unwrap_or_else(|| "—".to_string()),
-                    &noun_entry.gen_pl_def.unwrap_or_else(|| "—".to_string()),
-                ],
-            )
-            .unwrap(),
-        ),
-        _ => None,
+    /// Add a note to the deck with this full name, along with the names of
+    /// the media files it uses.
+    fn add(
+        &mut self,
+        deck: &str,
+        note: Note,
+        entry: Entry,
+        files: Vec<String>,
+        registry: &mut IdRegistry,
+        config: &AppConfig,
+    ) {
+        if let Some(delta) = &mut self.delta {
+            if self.previous.is_none_or(|previous| previous.is_outdated(&entry)) {
+                deck_for(delta, deck, registry, config).add_note(note.clone());
+                self.delta_files.extend(files);
+            }
+        }
+
+        deck_for(&mut self.decks, deck, registry, config).add_note(note);
+        self.manifest.record(entry);
     }
 }
 
-fn verb(root: &str, bin_data: &BinData, definition: &str, model: &Model) -> Option<Note> {
-    match bin_data.verb(root) {
-        Some(verb_entry) => Some(
-            Note::new(
-                model.clone(),
-                vec![
-                    root,
-                    definition,
-                    &verb_entry.pres_ind_first_sg.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.pres_ind_second_sg.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.pres_ind_third_sg.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.pres_ind_first_pl.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.pres_ind_second_pl.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.pres_ind_third_pl.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.past_ind_first_sg.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.past_ind_second_sg.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.past_ind_third_sg.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.past_ind_first_pl.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.past_ind_second_pl.unwrap_or_else(|| "—".to_string()),
-                    &verb_entry.past_ind_third_pl.unwrap_or_else(|| "—".to_string()),
-                ],
-            )
-            .unwrap(),
-        ),
-        _ => None,
-    }
+/// Which note of a word this is, for reports, as in "hestur (noun)".
+fn note_label(key: &DictionaryKey) -> String {
+    format!("{} ({})", key.root, key.category.name())
 }
 
-fn pronoun(root: &str, bin_data: &BinData, definition: &str, model: &Model) -> Option<Note> {
-    match bin_data.pronoun(root) {
-        Some(pronoun_entry) => Some(
-            Note::new(
-                model.clone(),
-                vec![
-                    root,
-                    definition,
-                    &pronoun_entry.nom.unwrap_or_else(|| "—".to_string()),
-                    &pronoun_entry.acc.unwrap_or_else(|| "—".to_string()),
-                    &pronoun_entry.dat.unwrap_or_else(|| "—".to_string()),
-                    &pronoun_entry.gen.unwrap_or_else(|| "—".to_string()),
-                ],
-            )
-            .unwrap(),
-        ),
-        _ => None,
+/// The deck with this full name, which is made if it doesn't exist yet.
+fn deck_for<'a>(
+    decks: &'a mut BTreeMap<String, Deck>,
+    name: &str,
+    registry: &mut IdRegistry,
+    config: &AppConfig,
+) -> &'a mut Deck {
+    decks.entry(name.to_string()).or_insert_with(|| {
+        claim_id(registry, "deck", deck_id(name), name);
+        Deck::new(deck_id(name), name, &config.deck_description)
+    })
+}
+
+/// The tags of a note: those of its senses in the wordlist, followed by
+/// those derived from its data.
+fn note_tags(
+    key: &DictionaryKey,
+    senses: &[Sense],
+    bin_data: &BinData,
+    config: &AppConfig,
+) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let derived = derived_tags(key, senses, bin_data, config);
+    for tag in senses.iter().flat_map(|sense| &sense.tags).chain(&derived) {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
     }
+    tags
 }
 
-fn simple_note(root: &str, definition: &str, model: &Model) -> Option<Note> {
-    Some(Note::new(model.clone(), vec![root, definition]).unwrap())
+/// The forms of a word for each of the chosen drill cells of its kind that
+/// BÍN has.
+fn drill_forms<'a>(
+    key: &DictionaryKey,
+    bin_data: &BinData,
+    cells: &'a [Cell],
+) -> Vec<(&'a Cell, String)> {
+    let (kind, forms) = match paradigm_forms(key, bin_data) {
+        Some(paradigm) => paradigm,
+        None => return Vec::new(),
+    };
+
+    cells
+        .iter()
+        .filter(|cell| cell.kind == kind)
+        .filter_map(|cell| Some((cell, forms.get(cell.index)?.clone()?)))
+        .collect()
 }
 
-/// Read application config from command line arguments.
-fn app_config(project_dirs: &ProjectDirs) -> AppConfig {
-    let arg_matches = App::new("Icelandic Anki Flashcard Generator")
-        .version("1.0")
-        .author("Seth Morabito")
-        .arg(
-            Arg::with_name("output")
-                .help("Anki deck output file")
-                .long("output")
-                .value_name("FILE")
-                .takes_value(true)
-                .default_value("deck.apkg")
-                .required(false),
-        )
-        .arg(
-            Arg::with_name("name")
+/// The kind of paradigm a word has, and its forms in the order of the kind's
+/// cells, if it's a noun, adjective, or verb that BÍN has.
+fn paradigm_forms(
+    key: &DictionaryKey,
+    bin_data: &BinData,
+) -> Option<(DrillKind, Vec<Option<String>>)> {
+    let owned = |forms: Vec<Option<&str>>| -> Vec<Option<String>> {
+        forms.into_iter().map(|form| form.map(str::to_string)).collect()
+    };
+
+    let (kind, forms) = match key.category {
+        Category::Noun => (
+            DrillKind::Noun,
+            bin_data.noun(&key.root, key.bin_id).map(|noun| owned(noun_forms(&noun))),
+        ),
+        Category::Adjective => (
+            DrillKind::Adjective,
+            bin_data
+                .adjective(&key.root, key.bin_id)
+                .map(|adjective| owned(adjective_forms(&adjective))),
+        ),
+        Category::Verb => (
+            DrillKind::Verb,
+            bin_data.verb(&key.root, key.bin_id).map(|verb| owned(verb_forms(&verb))),
+        ),
+        _ => return None,
+    };
+
+    Some((kind, forms?))
+}
+
+/// The Pronunciation field of a word's notes: the IPA transcription of its
+/// root, followed with `--ipa forms` by those of its principal parts, as in
+/// "[ˈhɛstʏr] · hests [hɛsts] · hestar [ˈhɛstar]".
+fn pronunciation(
+    key: &DictionaryKey,
+    bin_data: &BinData,
+    pronunciations: &Pronunciations,
+    config: &AppConfig,
+) -> String {
+    let transcription = |text: &str| format!("[{}]", pronunciations.transcribe(text));
+
+    match config.ipa {
+        IpaMode::None => String::new(),
+        IpaMode::Root => transcription(&key.root),
+        IpaMode::Forms => {
+            let mut parts = vec![transcription(&key.root)];
+            if let Some((kind, forms)) = paradigm_forms(key, bin_data) {
+                let principal = cells(kind)
+                    .into_iter()
+                    .filter(|cell| PRINCIPAL_PARTS.contains(&cell.name.as_str()))
+                    .filter_map(|cell| forms[cell.index].clone());
+                for form in principal {
+                    parts.push(format!("{} {}", form, transcription(&form)));
+                }
+            }
+            parts.join(" · ")
+        }
+    }
+}
+
+/// The Root, Definition, Word Class, and Table fields of a word's cloze
+/// note. The table is that of the word's own note type, with each chosen
+/// cell as a `{{c1::…}}` deletion, so that they are all blanked out on one
+/// card. There's no note if no cells are chosen.
+fn cloze_fields(
+    key: &DictionaryKey,
+    senses: &[Sense],
+    bin_data: &BinData,
+    selection: &ClozeSelection,
+) -> Option<Vec<String>> {
+    let (kind, forms) = paradigm_forms(key, bin_data)?;
+    let forms: Vec<Option<&str>> = forms.iter().map(Option::as_deref).collect();
+
+    let (word_class, template) = match kind {
+        DrillKind::Noun => {
+            let gender = noun_gender(key, bin_data).map(|gender| gender_name(&gender))?;
+            (format!("Noun — {}", gender), NOUN_TMPL)
+        }
+        DrillKind::Adjective => ("Adjective".to_string(), ADJ_TMPL),
+        DrillKind::Verb => ("Verb".to_string(), VERB_TMPL),
+    };
+
+    let cells = cells(kind);
+    let blanked = selection.select(&cells, &forms, &note_identity(key, senses));
+    if blanked.is_empty() {
+        return None;
+    }
+
+    // The table starts at the first heading, after the word class and
+    // definition. It's always cut from the built-in template, as an
+    // override may refer to fields the cloze note type doesn't have.
+    let mut table = template[template.find("<h3>")?..].to_string();
+    for cell in &cells {
+        if let Some(field) = &cell.field {
+            let form = forms[cell.index].unwrap_or("—");
+            let value = if blanked.contains(&cell) {
+                format!("{{{{c1::{}}}}}", form)
+            } else {
+                form.to_string()
+            };
+            table = table.replace(&format!("{{{{{}}}}}", field), &value);
+        }
+    }
+
+    Some(vec![key.root.clone(), definition_html(senses), word_class, table])
+}
+
+/// The full name of the deck a note goes into: the main deck, followed by a
+/// subdeck for each of `config.subdecks` that applies to the note, as in
+/// "Icelandic Vocabulary::Nouns::Masculine".
+fn deck_name(
+    key: &DictionaryKey,
+    senses: &[Sense],
+    bin_data: &BinData,
+    config: &AppConfig,
+) -> String {
+    let mut path = vec![config.deck_name.clone()];
+
+    for subdeck in &config.subdecks {
+        let name = match subdeck {
+            Subdeck::Category => Some(category_title(&key.category).to_string()),
+            Subdeck::Gender => {
+                noun_gender(key, bin_data).map(|gender| gender_name(&gender).to_string())
+            }
+            Subdeck::Section => senses.iter().find_map(|sense| sense.section.clone()),
+            Subdeck::Lesson => {
+                senses.iter().find_map(|sense| sense.lesson).map(|l| format!("Lesson {}", l))
+            }
+            Subdeck::Deck => {
+                let deck = senses.iter().find_map(|sense| sense.deck.clone());
+
+                // With `--sections deck`, notes without a deck path go into
+                // their section's subdeck instead, unless the sections are a
+                // level of their own.
+                match config.sections {
+                    SectionMode::Deck if !config.subdecks.contains(&Subdeck::Section) => {
+                        deck.or_else(|| senses.iter().find_map(|sense| sense.section.clone()))
+                    }
+                    _ => deck,
+                }
+            }
+        };
+
+        path.extend(name);
+    }
+
+    path.join("::")
+}
+
+/// The tags a note gets from its data, so that learners can build filtered
+/// decks in Anki: its category, gender, inflection class, irregularity,
+/// wordlist section, and BÍN word class. Tags are hierarchical, as in
+/// "gender::feminine", and can't contain spaces.
+fn derived_tags(
+    key: &DictionaryKey,
+    senses: &[Sense],
+    bin_data: &BinData,
+    config: &AppConfig,
+) -> Vec<String> {
+    let tag = |s: &str| {
+        s.replace(": ", "::")
+            .replace(", ", "_")
+            .replace(" (", "::")
+            .replace(')', "")
+            .replace(' ', "_")
+    };
+
+    let mut tags = vec![format!("category::{}", key.category.name())];
+
+    if let Some(gender) = noun_gender(key, bin_data) {
+        tags.push(format!("gender::{}", gender.name()));
+    }
+
+    let noun = match key.category {
+        Category::Noun => bin_data.noun(&key.root, key.bin_id),
+        Category::ProperNoun => bin_data.proper_noun(&key.root, key.bin_id).map(|name| name.noun),
+        _ => None,
+    };
+    if let Some(noun) = &noun {
+        match noun.is_weak() {
+            Some(true) => tags.push("declension::weak".to_string()),
+            Some(false) => tags.push("declension::strong".to_string()),
+            None => {}
+        }
+    }
+
+    if let Some(class) = inflection_class(key, bin_data) {
+        let kind = if key.category == Category::Verb { "conjugation" } else { "declension" };
+        tags.push(format!("{}::{}", kind, tag(&class)));
+    }
+
+    let irregular = match key.category {
+        Category::Verb => is_irregular_verb(&key.root),
+        _ => noun.and_then(|noun| noun.is_irregular()).unwrap_or(false),
+    };
+    if irregular {
+        tags.push("irregular".to_string());
+    }
+
+    if let SectionMode::Tag = config.sections {
+        if let Some(section) = senses.iter().find_map(|sense| sense.section.as_ref()) {
+            tags.push(format!("section::{}", tag(section)));
+        }
+    }
+
+    // The word class of a paradigm that matches the note's category.
+    let word_class = bin_data
+        .word_classes(&key.root, key.bin_id)
+        .into_iter()
+        .find(|&class| Category::from_word_classes(&[class]).contains(&key.category));
+    if let Some((word_class, _)) = word_class {
+        tags.push(format!("bin::{}", word_class));
+    }
+
+    tags
+}
+
+/// The name of a category, as a deck name.
+fn category_title(category: &Category) -> &'static str {
+    match category {
+        Category::Noun => "Nouns",
+        Category::ProperNoun => "Proper Nouns",
+        Category::Adjective => "Adjectives",
+        Category::Verb => "Verbs",
+        Category::Adverb => "Adverbs",
+        Category::Phrase => "Phrases",
+        Category::Pronoun => "Pronouns",
+        Category::IndefinitePronoun => "Indefinite Pronouns",
+        Category::Number => "Numbers",
+        Category::Preposition => "Prepositions",
+    }
+}
+
+/// The gender of a noun or proper noun, if BÍN has it.
+fn noun_gender(key: &DictionaryKey, bin_data: &BinData) -> Option<Gender> {
+    match key.category {
+        Category::Noun => bin_data.noun(&key.root, key.bin_id).map(|noun| noun.gender),
+        Category::ProperNoun => {
+            bin_data.proper_noun(&key.root, key.bin_id).map(|name| name.noun.gender)
+        }
+        _ => None,
+    }
+}
+
+/// Record the id of a deck or note type, warning if it was used for one with
+/// another name before. Anki merges decks and note types that share an id.
+fn claim_id(registry: &mut IdRegistry, kind: &str, id: usize, name: &str) {
+    if let Some(previous) = registry.claim(kind, id, name) {
+        println!(
+            "WARNING: The {} \"{}\" has id {}, which was used before for \"{}\". \
+             Anki will merge them when importing.",
+            kind, name, id, previous
+        );
+    }
+}
+
+/// Put the words of the dictionary into the order chosen by `config.sort`.
+/// Sorting is stable, so words that compare equal keep their input order.
+fn sort_dictionary(
+    dictionary: &mut Dictionary,
+    bin_data: &BinData,
+    config: &AppConfig,
+) -> Result<(), ProgramError> {
+    match config.sort {
+        SortMode::Input => {}
+        SortMode::Alphabetical => dictionary
+            .entries
+            .sort_by(|a, _, b, _| collation_key(&a.root).cmp(&collation_key(&b.root))),
+        SortMode::Frequency => {
+            let frequencies = match &config.frequencies {
+                Some(path) => Frequencies::load(File::open(path)?)?,
+                None => Frequencies::default(),
+            };
+
+            // Frequency lists are often in lower case, even for names.
+            // Words missing from the list go last.
+            let rank = |key: &DictionaryKey| {
+                frequencies
+                    .rank(&key.root)
+                    .or_else(|| frequencies.rank(&key.root.to_lowercase()))
+                    .unwrap_or(usize::MAX)
+            };
+
+            dictionary.entries.sort_by(|a, _, b, _| rank(a).cmp(&rank(b)));
+        }
+        SortMode::Declension => {
+            let classes: HashMap<DictionaryKey, Option<String>> = dictionary
+                .entries
+                .keys()
+                .map(|key| (key.clone(), inflection_class(key, bin_data)))
+                .collect();
+
+            // Words are grouped by category, then by class, with the words
+            // that have no class last in each category.
+            let order = |key: &DictionaryKey| {
+                let class = &classes[key];
+                (key.category.clone(), class.is_none(), class.clone())
+            };
+
+            dictionary.entries.sort_by(|a, _, b, _| order(a).cmp(&order(b)));
+        }
+    }
+
+    Ok(())
+}
+
+/// The declension or conjugation class of a word, if BÍN has a paradigm
+/// for it and its category inflects.
+fn inflection_class(key: &DictionaryKey, bin_data: &BinData) -> Option<String> {
+    match key.category {
+        Category::Noun => bin_data.noun(&key.root, key.bin_id)?.declension_class(),
+        Category::ProperNoun => {
+            bin_data.proper_noun(&key.root, key.bin_id)?.noun.declension_class()
+        }
+        Category::Adjective => bin_data.adjective(&key.root, key.bin_id)?.declension_class(),
+        Category::Verb => bin_data.verb(&key.root, key.bin_id)?.conjugation_class(),
+        _ => None,
+    }
+}
+
+/// What identifies a note: the explicit id of its senses if they have one,
+/// otherwise its root, category, and BÍN id. Definitions and other fields
+/// can change without changing the note's identity.
+fn note_identity(key: &DictionaryKey, senses: &[Sense]) -> String {
+    match senses.iter().find_map(|sense| sense.id.as_ref()) {
+        Some(id) => format!("id:{}", id),
+        None => {
+            let bin_id = key.bin_id.map(|id| id.to_string()).unwrap_or_default();
+            format!("{}|{}|{}", key.root, key.category.name(), bin_id)
+        }
+    }
+}
+
+/// The GUID of a note, from the parts of its identity. In a deck with a
+/// model namespace, the namespace is one of the parts, so that the same word
+/// in two decks makes two notes in Anki. Without one, notes keep the GUIDs
+/// they have always had.
+fn deck_note_guid(config: &AppConfig, parts: &[&str]) -> String {
+    let mut parts = parts.to_vec();
+    if let Some(namespace) = &config.model_namespace {
+        parts.insert(0, namespace);
+    }
+    note_guid(&parts)
+}
+
+/// The audio of a word: a recording named in the wordlist, or found by root
+/// in the media directory, or else generated by the text-to-speech engine.
+fn word_audio(key: &DictionaryKey, senses: &[Sense], media: &mut Media) -> Option<String> {
+    let named = senses.iter().find_map(|sense| sense.audio.as_deref());
+
+    match media.find(named, &key.root, &AUDIO_EXTENSIONS) {
+        Ok(Some(audio)) => Some(audio),
+        Ok(None) => speak(&key.root, media),
+        Err(missing) => {
+            println!(
+                "WARNING: Audio file {} for {} is not in the media directory.",
+                missing, key.root
+            );
+            None
+        }
+    }
+}
+
+/// The forms of a word in BÍN, each with its tag, from the paradigm of its
+/// category. Words that aren't in BÍN, such as phrases, only have their
+/// root, without a tag.
+fn word_forms<'a>(key: &'a DictionaryKey, bin_data: &'a BinData) -> Vec<(&'a str, &'a str)> {
+    let id = key.bin_id.or_else(|| {
+        bin_data
+            .paradigms(&key.root)
+            .into_iter()
+            .find(|&(_, word_class, common)| {
+                Category::from_word_classes(&[(word_class, common)]).contains(&key.category)
+            })
+            .map(|(id, _, _)| id)
+    });
+
+    match id.map(|id| bin_data.forms(&key.root, id)) {
+        Some(forms) if !forms.is_empty() => forms,
+        _ => vec![(key.root.as_str(), "")],
+    }
+}
+
+/// Example sentences for a word from the corpus, if there is one, with the
+/// form of the word in each highlighted and its grammatical tag shown.
+fn corpus_examples(
+    key: &DictionaryKey,
+    bin_data: &BinData,
+    corpus: Option<&Corpus>,
+) -> Vec<Example> {
+    let corpus = match corpus {
+        Some(corpus) => corpus,
+        None => return Vec::new(),
+    };
+
+    let forms = word_forms(key, bin_data);
+    let found = corpus.find(&forms, CORPUS_EXAMPLES);
+
+    found
+        .into_iter()
+        .map(|found| {
+            let text = &found.sentence.text;
+            let mut tags: Vec<String> = found.tags.iter().map(|tag| describe_tag(tag)).collect();
+            tags.retain(|tag| !tag.is_empty());
+            tags.dedup();
+
+            let mut html = format!(
+                "{}<span class=\"target\">{}</span>{}",
+                escape_html(&text[..found.start]),
+                escape_html(&text[found.start..found.end]),
+                escape_html(&text[found.end..])
+            );
+            if !tags.is_empty() {
+                html.push_str(&format!(" <span class=\"form-tag\">{}</span>", tags.join(" / ")));
+            }
+
+            let translation = found.sentence.translation.as_deref().map(escape_html);
+            Example { text: html, translation }
+        })
+        .collect()
+}
+
+/// Escape text for use in a field, which Anki reads as HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// The image of a word: a file named in the wordlist, or found by root in
+/// the media directory.
+fn word_image(key: &DictionaryKey, senses: &[Sense], media: &mut Media) -> Option<String> {
+    let named = senses.iter().find_map(|sense| sense.image.as_deref());
+
+    match media.find(named, &key.root, &IMAGE_EXTENSIONS) {
+        Ok(image) => image,
+        Err(missing) => {
+            println!(
+                "WARNING: Image file {} for {} is not in the media directory.",
+                missing, key.root
+            );
+            None
+        }
+    }
+}
+
+/// Generated audio for a text, if there's a text-to-speech engine that
+/// can speak it.
+fn speak(text: &str, media: &mut Media) -> Option<String> {
+    match media.speak(text) {
+        Ok(audio) => audio,
+        Err(message) => {
+            println!("WARNING: No audio generated for {}: {}.", text, message);
+            None
+        }
+    }
+}
+
+/// Render the definition field for one or more senses of a word. A single
+/// sense is shown as-is, several senses become a numbered list. Cases the
+/// word governs are shown before the definition, as in "+ dat. on, at".
+fn definition_html(senses: &[Sense]) -> String {
+    let sense_html = |sense: &Sense| {
+        let definition = if sense.definition.is_empty() { "—" } else { &sense.definition };
+
+        if sense.governs.is_empty() {
+            definition.to_string()
+        } else {
+            let cases = sense.governs.iter().map(|c| c.abbreviation()).collect::<Vec<_>>();
+            format!("<span class=\"governs\">+ {}</span> {}", cases.join("/"), definition)
+        }
+    };
+
+    match senses {
+        [sense] => sense_html(sense),
+        _ => format!(
+            "<ol class=\"senses\">{}</ol>",
+            senses
+                .iter()
+                .map(|sense| format!("<li>{}</li>", sense_html(sense)))
+                .collect::<String>()
+        ),
+    }
+}
+
+/// Values for the fields from `extra_fields()`, with the word's audio and
+/// image files. The examples are those of the senses, or if they have none,
+/// those `found` in the corpus. When several senses share a note, their
+/// examples and notes are listed one per line, and the first lesson found
+/// is used.
+fn extra_values(
+    senses: &[Sense],
+    found: &[Example],
+    audio: Option<String>,
+    image: Option<String>,
+) -> Vec<String> {
+    let mut examples: Vec<&Example> = senses.iter().flat_map(|sense| &sense.examples).collect();
+    if examples.is_empty() {
+        examples.extend(found);
+    }
+    let joined = |values: Vec<&str>| values.join("<br>");
+
+    vec![
+        joined(examples.iter().map(|e| e.text.as_str()).collect()),
+        joined(examples.iter().filter_map(|e| e.translation.as_deref()).collect()),
+        joined(senses.iter().filter_map(|sense| sense.notes.as_deref()).collect()),
+        audio.map(|a| format!("[sound:{}]", a)).unwrap_or_default(),
+        image.map(|i| format!("<img src=\"{}\">", i)).unwrap_or_default(),
+        senses.iter().find_map(|sense| sense.lesson).map(|l| l.to_string()).unwrap_or_default(),
+    ]
+}
+
+fn number(
+    root: &str,
+    id: Option<u64>,
+    bin_data: &BinData,
+    definition: &str,
+) -> Option<Vec<String>> {
+    match bin_data.number(root, id) {
+        Some(entry) => Some(vec![
+            root.to_string(),
+            definition.to_string(),
+            entry.masc_nom.unwrap_or_else(|| "—".to_string()),
+            entry.masc_acc.unwrap_or_else(|| "—".to_string()),
+            entry.masc_dat.unwrap_or_else(|| "—".to_string()),
+            entry.masc_gen.unwrap_or_else(|| "—".to_string()),
+            entry.fem_nom.unwrap_or_else(|| "—".to_string()),
+            entry.fem_acc.unwrap_or_else(|| "—".to_string()),
+            entry.fem_dat.unwrap_or_else(|| "—".to_string()),
+            entry.fem_gen.unwrap_or_else(|| "—".to_string()),
+            entry.neut_nom.unwrap_or_else(|| "—".to_string()),
+            entry.neut_acc.unwrap_or_else(|| "—".to_string()),
+            entry.neut_dat.unwrap_or_else(|| "—".to_string()),
+            entry.neut_gen.unwrap_or_else(|| "—".to_string()),
+        ]),
+        _ => None,
+    }
+}
+
+fn indefinite_pronoun(
+    root: &str,
+    id: Option<u64>,
+    bin_data: &BinData,
+    definition: &str,
+) -> Option<Vec<String>> {
+    match bin_data.indefinite_pronoun(root, id) {
+        Some(entry) => Some(vec![
+            root.to_string(),
+            definition.to_string(),
+            "Indefinite Pronoun".to_string(),
+            entry.masc_nom_sg.unwrap_or_else(|| "—".to_string()),
+            entry.masc_acc_sg.unwrap_or_else(|| "—".to_string()),
+            entry.masc_dat_sg.unwrap_or_else(|| "—".to_string()),
+            entry.masc_gen_sg.unwrap_or_else(|| "—".to_string()),
+            entry.fem_nom_sg.unwrap_or_else(|| "—".to_string()),
+            entry.fem_acc_sg.unwrap_or_else(|| "—".to_string()),
+            entry.fem_dat_sg.unwrap_or_else(|| "—".to_string()),
+            entry.fem_gen_sg.unwrap_or_else(|| "—".to_string()),
+            entry.neut_nom_sg.unwrap_or_else(|| "—".to_string()),
+            entry.neut_acc_sg.unwrap_or_else(|| "—".to_string()),
+            entry.neut_dat_sg.unwrap_or_else(|| "—".to_string()),
+            entry.neut_gen_sg.unwrap_or_else(|| "—".to_string()),
+            entry.masc_nom_pl.unwrap_or_else(|| "—".to_string()),
+            entry.masc_acc_pl.unwrap_or_else(|| "—".to_string()),
+            entry.masc_dat_pl.unwrap_or_else(|| "—".to_string()),
+            entry.masc_gen_pl.unwrap_or_else(|| "—".to_string()),
+            entry.fem_nom_pl.unwrap_or_else(|| "—".to_string()),
+            entry.fem_acc_pl.unwrap_or_else(|| "—".to_string()),
+            entry.fem_dat_pl.unwrap_or_else(|| "—".to_string()),
+            entry.fem_gen_pl.unwrap_or_else(|| "—".to_string()),
+            entry.neut_nom_pl.unwrap_or_else(|| "—".to_string()),
+            entry.neut_acc_pl.unwrap_or_else(|| "—".to_string()),
+            entry.neut_dat_pl.unwrap_or_else(|| "—".to_string()),
+            entry.neut_gen_pl.unwrap_or_else(|| "—".to_string()),
+        ]),
+        _ => None,
+    }
+}
+
+fn adjective(
+    root: &str,
+    id: Option<u64>,
+    bin_data: &BinData,
+    definition: &str,
+) -> Option<Vec<String>> {
+    match bin_data.adjective(root, id) {
+        Some(adjective_entry) => Some(vec![
+            root.to_string(),
+            definition.to_string(),
+            "Adjective".to_string(),
+            adjective_entry.masc_nom_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.fem_nom_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.neut_nom_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.masc_acc_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.fem_acc_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.neut_acc_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.masc_dat_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.fem_dat_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.neut_dat_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.masc_gen_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.fem_gen_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.neut_gen_sg_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.masc_nom_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.fem_nom_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.neut_nom_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.masc_acc_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.fem_acc_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.neut_acc_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.masc_dat_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.fem_dat_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.neut_dat_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.masc_gen_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.fem_gen_pl_strong.unwrap_or_else(|| "—".to_string()),
+            adjective_entry.neut_gen_pl_strong.unwrap_or_else(|| "—".to_string()),
+        ]),
+        _ => None,
+    }
+}
+
+fn noun(root: &str, id: Option<u64>, bin_data: &BinData, definition: &str) -> Option<Vec<String>> {
+    match bin_data.noun(root, id) {
+        Some(noun_entry) => Some(vec![
+            root.to_string(),
+            definition.to_string(),
+            gender_name(&noun_entry.gender).to_string(),
+            noun_entry.nom_sg.unwrap_or_else(|| "—".to_string()),
+            noun_entry.nom_sg_def.unwrap_or_else(|| "—".to_string()),
+            noun_entry.acc_sg.unwrap_or_else(|| "—".to_string()),
+            noun_entry.acc_sg_def.unwrap_or_else(|| "—".to_string()),
+            noun_entry.dat_sg.unwrap_or_else(|| "—".to_string()),
+            noun_entry.dat_sg_def.unwrap_or_else(|| "—".to_string()),
+            noun_entry.gen_sg.unwrap_or_else(|| "—".to_string()),
+            noun_entry.gen_sg_def.unwrap_or_else(|| "—".to_string()),
+            noun_entry.nom_pl.unwrap_or_else(|| "—".to_string()),
+            noun_entry.nom_pl_def.unwrap_or_else(|| "—".to_string()),
+            noun_entry.acc_pl.unwrap_or_else(|| "—".to_string()),
+            noun_entry.acc_pl_def.unwrap_or_else(|| "—".to_string()),
+            noun_entry.dat_pl.unwrap_or_else(|| "—".to_string()),
+            noun_entry.dat_pl_def.unwrap_or_else(|| "—".to_string()),
+            noun_entry.gen_pl.unwrap_or_else(|| "—".to_string()),
+            noun_entry.gen_pl_def.unwrap_or_else(|| "—".to_string()),
+        ]),
+        _ => None,
+    }
+}
+
+fn gender_name(gender: &Gender) -> &'static str {
+    match gender {
+        Gender::Masculine => "Masculine",
+        Gender::Feminine => "Feminine",
+        Gender::Neuter => "Neuter",
+    }
+}
+
+/// Names are often defective (singular only, or definite plural only, as in
+/// "Bandaríkin"), so missing forms are left empty rather than filled with a
+/// dash. The proper noun template hides any table whose nominative is empty.
+fn proper_noun(
+    root: &str,
+    id: Option<u64>,
+    bin_data: &BinData,
+    definition: &str,
+) -> Option<Vec<String>> {
+    match bin_data.proper_noun(root, id) {
+        Some(entry) => {
+            let name_type = match entry.kind {
+                NameKind::Personal => "Personal Name",
+                NameKind::Place => "Place Name",
+                NameKind::Company => "Company Name",
+                NameKind::Other => "Proper Noun",
+            };
+
+            let NounEntry {
+                gender,
+                nom_sg,
+                acc_sg,
+                dat_sg,
+                gen_sg,
+                nom_pl,
+                acc_pl,
+                dat_pl,
+                gen_pl,
+                nom_sg_def,
+                acc_sg_def,
+                dat_sg_def,
+                gen_sg_def,
+                nom_pl_def,
+                acc_pl_def,
+                dat_pl_def,
+                gen_pl_def,
+            } = entry.noun;
+
+            Some(vec![
+                root.to_string(),
+                definition.to_string(),
+                name_type.to_string(),
+                gender_name(&gender).to_string(),
+                nom_sg.unwrap_or_default(),
+                nom_sg_def.unwrap_or_default(),
+                acc_sg.unwrap_or_default(),
+                acc_sg_def.unwrap_or_default(),
+                dat_sg.unwrap_or_default(),
+                dat_sg_def.unwrap_or_default(),
+                gen_sg.unwrap_or_default(),
+                gen_sg_def.unwrap_or_default(),
+                nom_pl.unwrap_or_default(),
+                nom_pl_def.unwrap_or_default(),
+                acc_pl.unwrap_or_default(),
+                acc_pl_def.unwrap_or_default(),
+                dat_pl.unwrap_or_default(),
+                dat_pl_def.unwrap_or_default(),
+                gen_pl.unwrap_or_default(),
+                gen_pl_def.unwrap_or_default(),
+            ])
+        }
+        _ => None,
+    }
+}
+
+fn verb(root: &str, id: Option<u64>, bin_data: &BinData, definition: &str) -> Option<Vec<String>> {
+    match bin_data.verb(root, id) {
+        Some(verb_entry) => Some(vec![
+            root.to_string(),
+            definition.to_string(),
+            verb_entry.pres_ind_first_sg.unwrap_or_else(|| "—".to_string()),
+            verb_entry.pres_ind_second_sg.unwrap_or_else(|| "—".to_string()),
+            verb_entry.pres_ind_third_sg.unwrap_or_else(|| "—".to_string()),
+            verb_entry.pres_ind_first_pl.unwrap_or_else(|| "—".to_string()),
+            verb_entry.pres_ind_second_pl.unwrap_or_else(|| "—".to_string()),
+            verb_entry.pres_ind_third_pl.unwrap_or_else(|| "—".to_string()),
+            verb_entry.past_ind_first_sg.unwrap_or_else(|| "—".to_string()),
+            verb_entry.past_ind_second_sg.unwrap_or_else(|| "—".to_string()),
+            verb_entry.past_ind_third_sg.unwrap_or_else(|| "—".to_string()),
+            verb_entry.past_ind_first_pl.unwrap_or_else(|| "—".to_string()),
+            verb_entry.past_ind_second_pl.unwrap_or_else(|| "—".to_string()),
+            verb_entry.past_ind_third_pl.unwrap_or_else(|| "—".to_string()),
+        ]),
+        _ => None,
+    }
+}
+
+fn pronoun(root: &str, bin_data: &BinData, definition: &str) -> Option<Vec<String>> {
+    match bin_data.pronoun(root) {
+        Some(pronoun_entry) => Some(vec![
+            root.to_string(),
+            definition.to_string(),
+            pronoun_entry.nom.unwrap_or_else(|| "—".to_string()),
+            pronoun_entry.acc.unwrap_or_else(|| "—".to_string()),
+            pronoun_entry.dat.unwrap_or_else(|| "—".to_string()),
+            pronoun_entry.gen.unwrap_or_else(|| "—".to_string()),
+        ]),
+        _ => None,
+    }
+}
+
+fn simple_note(root: &str, definition: &str) -> Option<Vec<String>> {
+    Some(vec![root.to_string(), definition.to_string()])
+}
+
+/// Read application config from command line arguments.
+fn app_config(project_dirs: &ProjectDirs) -> AppConfig {
+    // Shared by the main command and `check`.
+    let columns_arg = Arg::with_name("columns")
+        .help("Wordlist header names for columns, e.g. \"root=Icelandic,definition=English\"")
+        .long("columns")
+        .value_name("MAPPING")
+        .takes_value(true)
+        .validator(|m| m.parse::<ColumnMapping>().map(|_| ()))
+        .required(false);
+    let wordlist_arg = Arg::with_name("wordlist")
+        .help("List of words, categories, and definitions (tab separated)")
+        .required(true);
+
+    let arg_matches = App::new("Icelandic Anki Flashcard Generator")
+        .version("1.0")
+        .author("Seth Morabito")
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .subcommand(
+            SubCommand::with_name("check")
+                .about("Checks a wordlist against BÍN, and exits non-zero if there are problems")
+                .arg(columns_arg.clone())
+                .arg(wordlist_arg.clone()),
+        )
+        .arg(
+            Arg::with_name("output")
+                .help("Anki deck output file")
+                .long("output")
+                .value_name("FILE")
+                .takes_value(true)
+                .default_value("deck.apkg")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("name")
                 .help("Anki deck name")
                 .long("name")
                 .value_name("NAME")
@@ -834,10 +2302,228 @@ fn app_config(project_dirs: &ProjectDirs) -> AppConfig {
                 .required(false),
         )
         .arg(
-            Arg::with_name("wordlist")
-                .help("List of words, categories, and definitions (tab separated)")
-                .required(true),
+            Arg::with_name("deck-id")
+                .help("Anki deck id [default: derived from the deck name]")
+                .long("deck-id")
+                .value_name("ID")
+                .takes_value(true)
+                .validator(|id| id.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("model-namespace")
+                .help("Namespace for the ids of the deck's note types [default: the deck name]")
+                .long("model-namespace")
+                .value_name("NAMESPACE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(columns_arg)
+        .arg(
+            Arg::with_name("senses")
+                .help("Merge senses of a word into one card, or give each its own card")
+                .long("senses")
+                .value_name("MODE")
+                .takes_value(true)
+                .possible_values(&["merge", "split"])
+                .default_value("merge")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("sections")
+                .help("Put words under a wordlist section into a subdeck, or tag them")
+                .long("sections")
+                .value_name("MODE")
+                .takes_value(true)
+                .possible_values(&["deck", "tag"])
+                .default_value("tag")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("subdecks")
+                .help(
+                    "Subdecks to put notes into, from the outermost, e.g. \"category,gender\". \
+                     Any of: category, gender, section, lesson, deck",
+                )
+                .long("subdecks")
+                .value_name("LEVELS")
+                .takes_value(true)
+                .default_value("deck")
+                .validator(|levels| parse_subdecks(&levels).map(|_| ()))
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("reverse")
+                .help(
+                    "Categories to also make English → Icelandic cards for, e.g. \"noun,verb\", \
+                     or \"all\"",
+                )
+                .long("reverse")
+                .value_name("CATEGORIES")
+                .takes_value(true)
+                .validator(|categories| parse_categories(&categories).map(|_| ()))
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("pictures")
+                .help(
+                    "Categories to also make picture → Icelandic cards for, e.g. \"noun\", for \
+                     the words that have an image",
+                )
+                .long("pictures")
+                .value_name("CATEGORIES")
+                .takes_value(true)
+                .validator(|categories| parse_categories(&categories).map(|_| ()))
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("drill")
+                .help(
+                    "Paradigm cells to make drill cards for, e.g. \"gen-sg,nom-pl,past-1sg\", \
+                     or \"noun\" for every cell of a kind",
+                )
+                .long("drill")
+                .value_name("CELLS")
+                .takes_value(true)
+                .validator(|cells| parse_cells(&cells).map(|_| ()))
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("answers")
+                .help("Whether drill cards are answered by flipping them, or by typing the form")
+                .long("answers")
+                .value_name("MODE")
+                .takes_value(true)
+                .possible_values(&["flip", "typed"])
+                .default_value("flip")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("cloze")
+                .help(
+                    "Paradigm cells to blank out on cloze cards, e.g. \"dat\" for every dative \
+                     cell, or \"random:4\"",
+                )
+                .long("cloze")
+                .value_name("CELLS")
+                .takes_value(true)
+                .validator(|cells| cells.parse::<ClozeSelection>().map(|_| ()))
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("templates")
+                .help("Directory of card templates and CSS overriding the built-in ones")
+                .long("templates")
+                .value_name("DIR")
+                .takes_value(true)
+                .validator(|dir| {
+                    if Path::new(&dir).is_dir() {
+                        Ok(())
+                    } else {
+                        Err(format!("{} is not a directory", dir))
+                    }
+                })
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("theme")
+                .help("Colours of the cards")
+                .long("theme")
+                .value_name("THEME")
+                .takes_value(true)
+                .possible_values(&["light", "dark", "high-contrast", "print"])
+                .default_value("light")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("media")
+                .help(
+                    "Directory of audio and image files to attach, named in the wordlist or \
+                     after the root",
+                )
+                .long("media")
+                .value_name("DIR")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("tts")
+                .help(
+                    "Text-to-speech command for words without a recording, e.g. \
+                     \"espeak-ng -v is -w {output} {text}\"",
+                )
+                .long("tts")
+                .value_name("COMMAND")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("tts-forms")
+                .help("Speak the form asked for on drill cards, rather than the root")
+                .long("tts-forms")
+                .requires("tts")
+                .required(false),
         )
+        .arg(
+            Arg::with_name("ipa")
+                .help("What to transcribe into IPA for the Pronunciation field")
+                .long("ipa")
+                .value_name("WHAT")
+                .takes_value(true)
+                .possible_values(&["none", "root", "forms"])
+                .default_value("root")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("ipa-exceptions")
+                .help("File of IPA transcriptions overriding the spelling rules")
+                .long("ipa-exceptions")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("corpus")
+                .help(
+                    "Sentences and their translations (tab separated) to take examples from, for \
+                     words without any",
+                )
+                .long("corpus")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("delta")
+                .help(
+                    "Also write the notes added or changed since the last build with a delta \
+                     to this file",
+                )
+                .long("delta")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("sort")
+                .help("Order of the notes in the deck")
+                .long("sort")
+                .value_name("ORDER")
+                .takes_value(true)
+                .possible_values(&["input", "alphabetical", "frequency", "declension"])
+                .default_value("input")
+                .requires_if("frequency", "frequencies")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("frequencies")
+                .help("Word frequency list, for sorting by frequency")
+                .long("frequencies")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(wordlist_arg)
         .get_matches();
 
     let bin_data: PathBuf = project_dirs.data_dir().join(DEFAULT_BIN_CSV);
@@ -852,25 +2538,365 @@ fn app_config(project_dirs: &ProjectDirs) -> AppConfig {
         None => DEFAULT_DECK_NAME.to_string(),
     };
 
+    // The default deck keeps the ids it has always had, so that decks
+    // generated before can still be updated.
+    let deck_id: usize = match arg_matches.value_of("deck-id") {
+        Some(id) => id.parse().unwrap(),
+        None if deck_name == DEFAULT_DECK_NAME => DECK_ID,
+        None => deck_id(&deck_name),
+    };
+
+    let model_namespace: Option<String> = match arg_matches.value_of("model-namespace") {
+        Some("") => None,
+        Some(namespace) => Some(namespace.to_string()),
+        None if deck_name == DEFAULT_DECK_NAME => None,
+        None => Some(deck_name.clone()),
+    };
+
     let deck_description: String = match arg_matches.value_of("description") {
         Some(description) => description.to_string(),
         None => DEFAULT_DECK_DESCRIPTION.to_string(),
     };
 
-    let wordlist: PathBuf = match arg_matches.value_of("wordlist") {
+    let senses = match arg_matches.value_of("senses") {
+        Some("split") => SenseMode::Split,
+        _ => SenseMode::Merge,
+    };
+
+    let sections = match arg_matches.value_of("sections") {
+        Some("deck") => SectionMode::Deck,
+        _ => SectionMode::Tag,
+    };
+
+    // Already checked by the argument's validator.
+    let mut subdecks = parse_subdecks(arg_matches.value_of("subdecks").unwrap_or("")).unwrap();
+    // With `--sections deck`, the sections are a level of their own, unless
+    // the deck column's level is there to hold them (see `deck_name`).
+    if let SectionMode::Deck = sections {
+        if !subdecks.contains(&Subdeck::Section) && !subdecks.contains(&Subdeck::Deck) {
+            subdecks.push(Subdeck::Section);
+        }
+    }
+
+    // Already checked by the argument's validator.
+    let reverse = parse_categories(arg_matches.value_of("reverse").unwrap_or("")).unwrap();
+
+    // Already checked by the argument's validator.
+    let pictures = parse_categories(arg_matches.value_of("pictures").unwrap_or("")).unwrap();
+
+    // Already checked by the argument's validator.
+    let drill = parse_cells(arg_matches.value_of("drill").unwrap_or("")).unwrap();
+
+    let answers = match arg_matches.value_of("answers") {
+        Some("typed") => AnswerMode::Typed,
+        _ => AnswerMode::Flip,
+    };
+
+    // Already checked by the argument's validator.
+    let cloze: Option<ClozeSelection> =
+        arg_matches.value_of("cloze").map(|cells| cells.parse().unwrap());
+
+    // Without the option, templates are overridden from the configuration
+    // directory, if there are any there.
+    let templates: Option<PathBuf> = match arg_matches.value_of("templates") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => Some(project_dirs.config_dir().join("templates")).filter(|dir| dir.is_dir()),
+    };
+
+    let theme = match arg_matches.value_of("theme") {
+        Some("dark") => Theme::Dark,
+        Some("high-contrast") => Theme::HighContrast,
+        Some("print") => Theme::Print,
+        _ => Theme::Light,
+    };
+
+    let media: Option<PathBuf> = arg_matches.value_of("media").map(PathBuf::from);
+
+    let tts: Option<String> = arg_matches.value_of("tts").map(str::to_string);
+    let tts_forms = arg_matches.is_present("tts-forms");
+    let tts_cache: PathBuf = project_dirs.cache_dir().join("tts");
+
+    let ipa = match arg_matches.value_of("ipa") {
+        Some("none") => IpaMode::None,
+        Some("forms") => IpaMode::Forms,
+        _ => IpaMode::Root,
+    };
+
+    // Without the option, exceptions are read from the configuration
+    // directory, if there are any there.
+    let ipa_exceptions: Option<PathBuf> = match arg_matches.value_of("ipa-exceptions") {
+        Some(file) => Some(PathBuf::from(file)),
+        None => Some(project_dirs.config_dir().join("ipa-exceptions.tsv")).filter(|f| f.is_file()),
+    };
+
+    let corpus: Option<PathBuf> = arg_matches.value_of("corpus").map(PathBuf::from);
+
+    let delta: Option<String> = arg_matches.value_of("delta").map(str::to_string);
+
+    let sort = match arg_matches.value_of("sort") {
+        Some("alphabetical") => SortMode::Alphabetical,
+        Some("frequency") => SortMode::Frequency,
+        Some("declension") => SortMode::Declension,
+        _ => SortMode::Input,
+    };
+
+    let frequencies: Option<PathBuf> = arg_matches.value_of("frequencies").map(PathBuf::from);
+
+    // The wordlist and its columns are given after `check` when checking.
+    let (command, wordlist_matches) = match arg_matches.subcommand() {
+        ("check", Some(check_matches)) => (Command::Check, check_matches),
+        _ => (Command::Build, &arg_matches),
+    };
+
+    // Already checked by the argument's validator.
+    let columns: ColumnMapping = match wordlist_matches.value_of("columns") {
+        Some(columns) => columns.parse().unwrap(),
+        None => ColumnMapping::default(),
+    };
+
+    let wordlist: PathBuf = match wordlist_matches.value_of("wordlist") {
         Some(wordlist) => Path::new(wordlist).to_path_buf(),
         None => Path::new("wordlist.txt").to_path_buf(),
     };
 
-    AppConfig { bin_data, output, deck_name, deck_description, wordlist }
+    let id_registry: PathBuf = project_dirs.data_dir().join(ID_REGISTRY);
+
+    AppConfig {
+        command,
+        bin_data,
+        id_registry,
+        output,
+        deck_name,
+        deck_id,
+        model_namespace,
+        deck_description,
+        senses,
+        sections,
+        subdecks,
+        reverse,
+        pictures,
+        drill,
+        answers,
+        cloze,
+        templates,
+        theme,
+        media,
+        tts,
+        tts_forms,
+        tts_cache,
+        ipa,
+        ipa_exceptions,
+        corpus,
+        delta,
+        sort,
+        frequencies,
+        columns,
+        wordlist,
+    }
+}
+
+/// How several senses of the same word are turned into notes.
+#[derive(Debug, Default)]
+enum SenseMode {
+    /// One note per word, with the senses in a numbered list.
+    #[default]
+    Merge,
+    /// One note per sense.
+    Split,
+}
+
+/// What the `## Section` headers of a wordlist become.
+#[derive(Debug, Default)]
+enum SectionMode {
+    /// A level of subdecks, as with `--subdecks section`.
+    Deck,
+    /// A tag on each note.
+    #[default]
+    Tag,
+}
+
+/// A level of subdecks under the main deck.
+#[derive(Debug, Eq, PartialEq)]
+enum Subdeck {
+    /// The category of the word, such as "Nouns".
+    Category,
+    /// The gender of nouns, such as "Masculine".
+    Gender,
+    /// The wordlist section the word is listed under.
+    Section,
+    /// The lesson number, as in "Lesson 3".
+    Lesson,
+    /// The deck column of the wordlist.
+    Deck,
+}
+
+/// Parse a comma-separated list of subdeck levels.
+fn parse_subdecks(levels: &str) -> Result<Vec<Subdeck>, String> {
+    levels
+        .split(',')
+        .map(str::trim)
+        .filter(|level| !level.is_empty())
+        .map(|level| match level {
+            "category" => Ok(Subdeck::Category),
+            "gender" => Ok(Subdeck::Gender),
+            "section" => Ok(Subdeck::Section),
+            "lesson" => Ok(Subdeck::Lesson),
+            "deck" => Ok(Subdeck::Deck),
+            _ => Err(format!("unknown subdeck level \"{}\"", level)),
+        })
+        .collect()
+}
+
+/// Parse a comma-separated list of categories, where "all" stands for every
+/// category.
+fn parse_categories(categories: &str) -> Result<Vec<Category>, String> {
+    let mut parsed = Vec::new();
+
+    for name in categories.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        if name == "all" {
+            parsed.extend(Category::ALL.iter().cloned());
+        } else {
+            parsed.push(name.parse().map_err(|_| format!("unknown category \"{}\"", name))?);
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// How drill cards are answered.
+#[derive(Debug, Default)]
+enum AnswerMode {
+    /// By recalling the form, and flipping the card to check it.
+    #[default]
+    Flip,
+    /// By typing the form, which Anki compares with the right one.
+    Typed,
+}
+
+impl AnswerMode {
+    /// The name of the mode, which is part of the identity of drill notes:
+    /// flip and typed notes have different note types, so they can't be
+    /// updated into one another.
+    fn name(&self) -> &'static str {
+        match self {
+            AnswerMode::Flip => "drill",
+            AnswerMode::Typed => "typed",
+        }
+    }
+}
+
+/// The colours of the cards. Every theme stays readable in Anki's night
+/// mode.
+#[derive(Debug, Default)]
+enum Theme {
+    /// Dark text on a light background, and the reverse in night mode.
+    #[default]
+    Light,
+    /// Light text on a dark background, in and out of night mode.
+    Dark,
+    /// Black and white with strong colours, inverted in night mode.
+    HighContrast,
+    /// Black on white without shading, for printing.
+    Print,
+}
+
+impl Theme {
+    /// The CSS variables that colour the cards.
+    fn css(&self) -> String {
+        let (day, night) = match self {
+            Theme::Light => (LIGHT_COLOURS, DARK_COLOURS),
+            Theme::Dark => (DARK_COLOURS, DARK_COLOURS),
+            Theme::HighContrast => (HIGH_CONTRAST_COLOURS, HIGH_CONTRAST_NIGHT_COLOURS),
+            Theme::Print => (PRINT_COLOURS, DARK_COLOURS),
+        };
+
+        format!(".card {{\n{}}}\n{} {{\n{}}}", day, NIGHT_MODE, night)
+    }
+}
+
+/// What the Pronunciation field of notes holds.
+#[derive(Debug, Default)]
+enum IpaMode {
+    /// Nothing.
+    None,
+    /// The IPA transcription of the root.
+    #[default]
+    Root,
+    /// The transcriptions of the root and its principal parts.
+    Forms,
 }
 
-#[derive(Debug)]
+/// The order of the notes in the deck.
+#[derive(Debug, Default)]
+enum SortMode {
+    /// The order of the wordlist.
+    #[default]
+    Input,
+    /// Icelandic alphabetical order of the roots.
+    Alphabetical,
+    /// The most common words first, from a frequency list.
+    Frequency,
+    /// By category, then by declension or conjugation class.
+    Declension,
+}
+
+/// What to do with the wordlist.
+#[derive(Debug, Default)]
+enum Command {
+    /// Generate an Anki deck.
+    #[default]
+    Build,
+    /// Check the wordlist, and report any problems.
+    Check,
+}
+
+#[derive(Debug, Default)]
 struct AppConfig {
+    command: Command,
     bin_data: PathBuf,
+    /// Where the ids of generated decks and note types are recorded.
+    id_registry: PathBuf,
     output: String,
     deck_name: String,
+    deck_id: usize,
+    /// None for the note types' original ids.
+    model_namespace: Option<String>,
     deck_description: String,
+    senses: SenseMode,
+    sections: SectionMode,
+    subdecks: Vec<Subdeck>,
+    /// The categories that also get reverse cards.
+    reverse: Vec<Category>,
+    /// The categories that also get picture cards.
+    pictures: Vec<Category>,
+    /// The paradigm cells to make drill cards for.
+    drill: Vec<Cell>,
+    answers: AnswerMode,
+    /// The cells to blank out on cloze cards, if there are any.
+    cloze: Option<ClozeSelection>,
+    /// The directory of template overrides, if there is one.
+    templates: Option<PathBuf>,
+    theme: Theme,
+    /// The directory media files are attached from, if there is one.
+    media: Option<PathBuf>,
+    /// The text-to-speech command template, if there is one.
+    tts: Option<String>,
+    /// Whether drill cards speak their forms.
+    tts_forms: bool,
+    /// Where generated audio is cached.
+    tts_cache: PathBuf,
+    ipa: IpaMode,
+    /// The list of exceptions to the IPA transcription rules, if there is
+    /// one.
+    ipa_exceptions: Option<PathBuf>,
+    /// The corpus example sentences are taken from, if there is one.
+    corpus: Option<PathBuf>,
+    /// Where to write the notes added or changed since the last build, if
+    /// anywhere.
+    delta: Option<String>,
+    sort: SortMode,
+    frequencies: Option<PathBuf>,
+    columns: ColumnMapping,
     wordlist: PathBuf,
 }
 
@@ -901,6 +2927,24 @@ async fn get_bin_csv(app_config: &AppConfig) -> Result<(), ProgramError> {
     Ok(())
 }
 
+/// Report how the notes of a build differ from those of the previous one.
+fn report_changes(changes: &Changes) {
+    println!(
+        "Since the last delta: {} added, {} changed, {} removed.",
+        changes.added.len(),
+        changes.changed.len(),
+        changes.removed.len()
+    );
+
+    for (change, entries) in
+        [("added", &changes.added), ("changed", &changes.changed), ("removed", &changes.removed)]
+    {
+        for entry in entries {
+            println!("  {}: {}", change, entry.label);
+        }
+    }
+}
+
 /// Ensure that the BIN CSV data file exists locally. If it does not exist,
 /// it will be downloaded and unzipped automatically.
 ///
@@ -965,17 +3009,76 @@ async fn main() -> Result<(), ProgramError> {
                 return Err(e);
             }
 
-            let dictionary = Dictionary::load(File::open(&config.wordlist)?)?;
-
             println!("Loading BIN Data...");
             let bin_data_file = File::open(&config.bin_data)?;
             let bin_data = BinData::load(bin_data_file)?;
 
+            let classify = |root: &str, id: Option<u64>| {
+                Category::from_word_classes(&bin_data.word_classes(root, id))
+            };
+            let mut dictionary =
+                Dictionary::from_path(&config.wordlist, &config.columns, &classify)?;
+
+            if let Command::Check = config.command {
+                let diagnostics = check(&dictionary, &bin_data);
+                for diagnostic in &diagnostics {
+                    println!("{}", diagnostic);
+                }
+
+                if diagnostics.is_empty() {
+                    println!("No problems found.");
+                    return Ok(());
+                }
+
+                println!("{} problem(s) found.", diagnostics.len());
+                std::process::exit(1);
+            }
+
+            for diagnostic in &dictionary.diagnostics {
+                println!("WARNING: {}", diagnostic);
+            }
+
+            sort_dictionary(&mut dictionary, &bin_data, &config)?;
+
             println!("Starting Anki deck generation...");
-            let deck = generate_deck(&dictionary, &bin_data, &config)?;
+            let mut registry = IdRegistry::load(&config.id_registry)?;
+            let tts =
+                config.tts.as_deref().map(|command| Tts::new(command, config.tts_cache.clone()));
+            let mut media = Media::new(config.media.as_deref(), tts);
+            let manifest_path = Path::new(&config.output).with_extension("manifest.tsv");
+            let previous = Manifest::load(&manifest_path)?;
+            let output = generate_deck(
+                &dictionary,
+                &bin_data,
+                &config,
+                &mut registry,
+                &mut media,
+                previous.as_ref(),
+            )?;
 
             println!("Saving Anki deck...");
-            deck.write_to_file(&config.output)?;
+            let decks = output.decks.into_values().collect();
+            Package::new(decks, media.files()?)?.write_to_file(&config.output)?;
+            registry.save()?;
+
+            if let Some(previous) = &previous {
+                report_changes(&output.manifest.changes(previous));
+            }
+
+            // The manifest is only updated along with a delta, so that builds
+            // in between, such as tests of the deck, don't keep their changes
+            // out of the next delta.
+            if let (Some(path), Some(delta)) = (&config.delta, output.delta) {
+                if delta.is_empty() {
+                    println!("No notes were added or changed, so there is no delta to save.");
+                } else {
+                    println!("Saving new and changed notes to {}...", path);
+                    let decks = delta.into_values().collect();
+                    let files = media.files_named(&output.delta_files)?;
+                    Package::new(decks, files)?.write_to_file(path)?;
+                }
+                output.manifest.save(&manifest_path)?;
+            }
 
             println!("Done!");
         }
@@ -984,3 +3087,133 @@ async fn main() -> Result<(), ProgramError> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIN_DATA: &str = "hestur;1;kk;alm;hestur;NFET
+hestur;1;kk;alm;hest;ÞFET
+hestur;1;kk;alm;hesti;ÞGFET
+hestur;1;kk;alm;hests;EFET
+hestur;1;kk;alm;hestar;NFFT
+maður;2;kk;alm;maður;NFET
+maður;2;kk;alm;mann;ÞFET
+maður;2;kk;alm;manni;ÞGFET
+maður;2;kk;alm;manns;EFET
+maður;2;kk;alm;menn;NFFT
+auga;3;hk;alm;auga;NFET
+auga;3;hk;alm;auga;ÞFET
+auga;3;hk;alm;auga;ÞGFET
+auga;3;hk;alm;auga;EFET
+auga;3;hk;alm;augu;NFFT
+";
+
+    fn config(sections: SectionMode) -> AppConfig {
+        AppConfig { sections, ..Default::default() }
+    }
+
+    #[test]
+    pub fn namespaces_note_guids() {
+        let identity = note_identity(&noun("hestur"), &[]);
+        let guid = |namespace: Option<&str>| {
+            let config =
+                AppConfig { model_namespace: namespace.map(str::to_string), ..Default::default() };
+            deck_note_guid(&config, &[&identity])
+        };
+
+        assert_eq!(note_guid(&["hestur|noun|"]), guid(None));
+        assert_ne!(guid(Some("Course 1")), guid(Some("Course 2")));
+        assert_ne!(guid(None), guid(Some("Course 1")));
+    }
+
+    #[test]
+    pub fn puts_sections_into_decks() {
+        let bin_data = BinData::load(BIN_DATA.as_bytes()).unwrap();
+        let config = AppConfig {
+            deck_name: "Icelandic".to_string(),
+            sections: SectionMode::Deck,
+            subdecks: vec![Subdeck::Deck],
+            ..Default::default()
+        };
+        let sense = |deck: Option<&str>| Sense {
+            deck: deck.map(str::to_string),
+            section: Some("Chapter 1".to_string()),
+            ..Default::default()
+        };
+
+        let key = noun("hestur");
+        assert_eq!(
+            "Icelandic::Animals",
+            deck_name(&key, &[sense(Some("Animals"))], &bin_data, &config)
+        );
+        assert_eq!("Icelandic::Chapter 1", deck_name(&key, &[sense(None)], &bin_data, &config));
+
+        let config = AppConfig { subdecks: vec![Subdeck::Deck, Subdeck::Section], ..config };
+        assert_eq!(
+            "Icelandic::Animals::Chapter 1",
+            deck_name(&key, &[sense(Some("Animals"))], &bin_data, &config)
+        );
+    }
+
+    #[test]
+    pub fn escapes_corpus_examples() {
+        let bin_data = BinData::load(BIN_DATA.as_bytes()).unwrap();
+        let corpus = Corpus::load("Hestar & \"kýr\" <3\tHorses & cows <3".as_bytes()).unwrap();
+
+        let examples = corpus_examples(&noun("hestur"), &bin_data, Some(&corpus));
+        assert_eq!(1, examples.len());
+        assert_eq!(
+            "<span class=\"target\">Hestar</span> &amp; &quot;kýr&quot; &lt;3 \
+             <span class=\"form-tag\">nominative plural</span>",
+            examples[0].text
+        );
+        assert_eq!(Some("Horses &amp; cows &lt;3"), examples[0].translation.as_deref());
+    }
+
+    fn noun(root: &str) -> DictionaryKey {
+        DictionaryKey { root: root.to_string(), category: Category::Noun, bin_id: None }
+    }
+
+    #[test]
+    pub fn derives_tags() {
+        let bin_data = BinData::load(BIN_DATA.as_bytes()).unwrap();
+        let senses =
+            vec![Sense { section: Some("Chapter 1::At home".to_string()), ..Default::default() }];
+
+        assert_eq!(
+            vec![
+                "category::noun",
+                "gender::masculine",
+                "declension::strong",
+                "declension::masculine::-ur_-s_-ar",
+                "section::Chapter_1::At_home",
+                "bin::kk",
+            ],
+            derived_tags(&noun("hestur"), &senses, &bin_data, &config(SectionMode::Tag))
+        );
+        assert_eq!(
+            vec![
+                "category::noun",
+                "gender::neuter",
+                "declension::weak",
+                "declension::neuter::-a_-a_-u",
+                "bin::hk",
+            ],
+            derived_tags(&noun("auga"), &senses, &bin_data, &config(SectionMode::Deck))
+        );
+
+        let tags = derived_tags(&noun("maður"), &[], &bin_data, &config(SectionMode::Tag));
+        assert!(tags.contains(&"irregular".to_string()));
+
+        let key = DictionaryKey {
+            root: "góðan daginn".to_string(),
+            category: Category::Phrase,
+            bin_id: None,
+        };
+        assert_eq!(
+            vec!["category::phrase"],
+            derived_tags(&key, &[], &bin_data, &config(SectionMode::Tag))
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Manifests of the notes generated by a build, kept next to its package,
+//! so that a rebuild can tell which notes are new, changed, or gone.
+
+use crate::ids::stable_hash;
+use crate::ProgramError;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// A note of a build.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub guid: String,
+    /// A hash of everything about the note that importing it would update:
+    /// its deck, fields, and tags. Its position in the deck is left out, so
+    /// that adding a word doesn't change every note after it.
+    pub hash: u64,
+    pub deck: String,
+    /// Which note this is, for reports, as in "hestur (noun)".
+    pub label: String,
+}
+
+impl Entry {
+    /// An entry for a note, where `fields[sequence]` holds the note's
+    /// position in the deck.
+    pub fn new(
+        guid: &str,
+        deck: &str,
+        label: &str,
+        fields: &[String],
+        sequence: usize,
+        tags: &[String],
+    ) -> Self {
+        let fields: Vec<&str> = fields
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != sequence)
+            .map(|(_, field)| field.as_str())
+            .collect();
+        let contents = format!("{}\x1e{}\x1e{}", deck, fields.join("\x1f"), tags.join("\x1f"));
+        Entry {
+            guid: guid.to_string(),
+            hash: stable_hash(&contents),
+            deck: deck.to_string(),
+            label: label.to_string(),
+        }
+    }
+}
+
+/// How the notes of a build differ from those of a previous one.
+#[derive(Debug, Default)]
+pub struct Changes<'a> {
+    pub added: Vec<&'a Entry>,
+    pub changed: Vec<&'a Entry>,
+    pub removed: Vec<&'a Entry>,
+}
+
+/// The notes of a build, in the order they were generated.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    entries: Vec<Entry>,
+    /// The position of each note's entry, by GUID.
+    positions: HashMap<String, usize>,
+}
+
+impl Manifest {
+    /// Read a manifest file, which holds a GUID, hash, deck, and label on
+    /// each line, separated by tabs. There's no manifest if the file is
+    /// missing.
+    pub fn load(path: &Path) -> Result<Option<Self>, ProgramError> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut manifest = Manifest::default();
+        for line in text.lines() {
+            let mut fields = line.splitn(4, '\t');
+            if let (Some(guid), Some(Ok(hash)), Some(deck), Some(label)) = (
+                fields.next(),
+                fields.next().map(|hash| u64::from_str_radix(hash, 16)),
+                fields.next(),
+                fields.next(),
+            ) {
+                let entry = Entry {
+                    guid: guid.to_string(),
+                    hash,
+                    deck: deck.to_string(),
+                    label: label.to_string(),
+                };
+                manifest.record(entry);
+            }
+        }
+
+        Ok(Some(manifest))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ProgramError> {
+        let lines: String = self
+            .entries
+            .iter()
+            .map(|e| format!("{}\t{:016x}\t{}\t{}\n", e.guid, e.hash, e.deck, e.label))
+            .collect();
+        std::fs::write(path, lines)?;
+        Ok(())
+    }
+
+    /// Record a note. A note recorded again replaces the earlier entry.
+    pub fn record(&mut self, entry: Entry) {
+        match self.positions.get(&entry.guid) {
+            Some(&position) => self.entries[position] = entry,
+            None => {
+                self.positions.insert(entry.guid.clone(), self.entries.len());
+                self.entries.push(entry);
+            }
+        }
+    }
+
+    /// Whether a note is new or has changed since this manifest's build.
+    pub fn is_outdated(&self, entry: &Entry) -> bool {
+        self.get(&entry.guid).is_none_or(|previous| previous.hash != entry.hash)
+    }
+
+    fn get(&self, guid: &str) -> Option<&Entry> {
+        self.positions.get(guid).map(|&position| &self.entries[position])
+    }
+
+    /// The notes added and changed since a previous build, in the order of
+    /// this one, and those removed since, in the order of the previous one.
+    pub fn changes<'a>(&'a self, previous: &'a Manifest) -> Changes<'a> {
+        let mut changes = Changes::default();
+
+        for entry in &self.entries {
+            match previous.get(&entry.guid) {
+                None => changes.added.push(entry),
+                Some(before) if before.hash != entry.hash => changes.changed.push(entry),
+                Some(_) => {}
+            }
+        }
+        changes.removed =
+            previous.entries.iter().filter(|entry| self.get(&entry.guid).is_none()).collect();
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered_entry(guid: &str, label: &str, definition: &str, sequence: usize) -> Entry {
+        let fields = [label.to_string(), definition.to_string(), format!("{:05}", sequence)];
+        Entry::new(guid, "Icelandic Vocabulary", label, &fields, 2, &["noun".to_string()])
+    }
+
+    fn entry(guid: &str, label: &str, definition: &str) -> Entry {
+        numbered_entry(guid, label, definition, 1)
+    }
+
+    #[test]
+    pub fn compares_builds() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deck.manifest.tsv");
+        assert!(Manifest::load(&path).unwrap().is_none());
+
+        let mut manifest = Manifest::default();
+        manifest.record(entry("a", "hestur", "horse"));
+        manifest.record(entry("b", "kona", "woman"));
+        manifest.record(entry("c", "borð", "table"));
+        manifest.save(&path).unwrap();
+        let previous = Manifest::load(&path).unwrap().unwrap();
+
+        let mut manifest = Manifest::default();
+        manifest.record(entry("d", "maður", "man"));
+        manifest.record(entry("a", "hestur", "horse"));
+        manifest.record(entry("b", "kona", "wife"));
+
+        assert!(!previous.is_outdated(&entry("a", "hestur", "horse")));
+        assert!(previous.is_outdated(&entry("b", "kona", "wife")));
+        assert!(previous.is_outdated(&entry("d", "maður", "man")));
+
+        let changes = manifest.changes(&previous);
+        let labels = |entries: &[&Entry]| -> Vec<String> {
+            entries.iter().map(|entry| entry.label.clone()).collect()
+        };
+        assert_eq!(vec!["maður"], labels(&changes.added));
+        assert_eq!(vec!["kona"], labels(&changes.changed));
+        assert_eq!(vec!["borð"], labels(&changes.removed));
+    }
+
+    #[test]
+    pub fn ignores_renumbering() {
+        let mut previous = Manifest::default();
+        previous.record(numbered_entry("a", "hestur", "horse", 1));
+        previous.record(numbered_entry("b", "kona", "woman", 2));
+        previous.record(numbered_entry("c", "borð", "table", 3));
+
+        let mut manifest = Manifest::default();
+        manifest.record(numbered_entry("a", "hestur", "horse", 1));
+        manifest.record(numbered_entry("d", "maður", "man", 2));
+        manifest.record(numbered_entry("b", "kona", "woman", 3));
+        manifest.record(numbered_entry("c", "borð", "table", 4));
+
+        let changes = manifest.changes(&previous);
+        assert_eq!(vec!["maður"], changes.added.iter().map(|e| &e.label).collect::<Vec<_>>());
+        assert!(changes.changed.is_empty());
+        assert!(changes.removed.is_empty());
+        assert!(!previous.is_outdated(&numbered_entry("c", "borð", "table", 4)));
+    }
+}
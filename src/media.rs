@@ -0,0 +1,160 @@
+//! Media files, such as recordings and pictures of words, attached to the
+//! notes of a deck and written into its package.
+
+use crate::tts::Tts;
+use crate::ProgramError;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the audio files Anki can play, in order of preference.
+pub const AUDIO_EXTENSIONS: [&str; 5] = ["mp3", "ogg", "opus", "m4a", "wav"];
+
+/// Extensions of the image files Anki can show, in order of preference.
+pub const IMAGE_EXTENSIONS: [&str; 6] = ["jpg", "jpeg", "png", "webp", "gif", "svg"];
+
+/// The media directory of a deck, and the files found in it so far.
+#[derive(Debug, Default)]
+pub struct Media {
+    dir: Option<PathBuf>,
+    /// The text-to-speech engine, if one is configured.
+    tts: Option<Tts>,
+    files: Vec<PathBuf>,
+}
+
+impl Media {
+    pub fn new(dir: Option<&Path>, tts: Option<Tts>) -> Self {
+        Media { dir: dir.map(Path::to_path_buf), tts, files: Vec::new() }
+    }
+
+    /// Speak a text with the text-to-speech engine, and add the audio to the
+    /// deck's media. Without an engine, there's no audio.
+    pub fn speak(&mut self, text: &str) -> Result<Option<String>, String> {
+        let path = match &mut self.tts {
+            Some(tts) => tts.speak(text)?,
+            None => None,
+        };
+
+        Ok(path.map(|path| self.add(&path)))
+    }
+
+    /// Find a word's media file: the file its wordlist entry names, if any,
+    /// otherwise a file named after its root with one of `extensions`, as
+    /// in "hestur.mp3". Files found in the media directory are added to the
+    /// deck's media, and their names returned for use in fields.
+    ///
+    /// Without a media directory, a named file is assumed to be in Anki's
+    /// media folder already. With one, a named file that isn't in it is an
+    /// error, with the name of the file.
+    pub fn find(
+        &mut self,
+        named: Option<&str>,
+        root: &str,
+        extensions: &[&str],
+    ) -> Result<Option<String>, String> {
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => return Ok(named.map(str::to_string)),
+        };
+
+        if let Some(named) = named {
+            return match dir.join(named) {
+                path if path.is_file() => Ok(Some(self.add(&path))),
+                _ => Err(named.to_string()),
+            };
+        }
+
+        let found = extensions
+            .iter()
+            .map(|extension| dir.join(format!("{}.{}", root, extension)))
+            .find(|path| path.is_file());
+
+        Ok(found.map(|path| self.add(&path)))
+    }
+
+    /// Add a file to the deck's media, and return its name in Anki's media
+    /// folder.
+    pub fn add(&mut self, path: &Path) -> String {
+        if !self.files.iter().any(|file| file == path) {
+            self.files.push(path.to_path_buf());
+        }
+
+        path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    /// The paths of the files to write into the package. Two files with the
+    /// same name, from different directories, are an error, as they would
+    /// overwrite each other in Anki's media folder.
+    pub fn files(&self) -> Result<Vec<&str>, ProgramError> {
+        for (i, file) in self.files.iter().enumerate() {
+            let other = self.files[..i].iter().find(|other| other.file_name() == file.file_name());
+            if let Some(other) = other {
+                return Err(ProgramError::Media(format!(
+                    "{} and {} have the same name",
+                    other.display(),
+                    file.display()
+                )));
+            }
+        }
+
+        Ok(self.files.iter().filter_map(|file| file.to_str()).collect())
+    }
+
+    /// The paths of the files with these names, to write into a package of
+    /// some of the deck's notes.
+    pub fn files_named(&self, names: &HashSet<String>) -> Result<Vec<&str>, ProgramError> {
+        let named = |file: &&str| {
+            Path::new(file)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| names.contains(name))
+        };
+        Ok(self.files()?.into_iter().filter(named).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    pub fn finds_media_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("hestur.ogg"), "").unwrap();
+        fs::write(dir.path().join("hestur.mp3"), "").unwrap();
+        fs::create_dir(dir.path().join("recordings")).unwrap();
+        fs::write(dir.path().join("recordings/kona-2.mp3"), "").unwrap();
+
+        let mut media = Media::new(Some(dir.path()), None);
+        assert_eq!(Ok(Some("hestur.mp3".to_string())), media.find(None, "hestur", &["mp3", "ogg"]));
+        assert_eq!(Ok(None), media.find(None, "kona", &["mp3"]));
+        assert_eq!(
+            Ok(Some("kona-2.mp3".to_string())),
+            media.find(Some("recordings/kona-2.mp3"), "kona", &["mp3"])
+        );
+        assert_eq!(Err("kona.wav".to_string()), media.find(Some("kona.wav"), "kona", &["mp3"]));
+        assert_eq!(Ok(Some("hestur.mp3".to_string())), media.find(None, "hestur", &["mp3"]));
+        assert_eq!(2, media.files().unwrap().len());
+        let names: HashSet<String> = vec!["kona-2.mp3".to_string()].into_iter().collect();
+        assert_eq!(1, media.files_named(&names).unwrap().len());
+
+        let mut without_dir = Media::new(None, None);
+        assert_eq!(Ok(Some("a.mp3".to_string())), without_dir.find(Some("a.mp3"), "a", &["mp3"]));
+        assert!(without_dir.files().unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn rejects_files_with_the_same_name() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("a")).unwrap();
+        fs::create_dir(dir.path().join("b")).unwrap();
+        fs::write(dir.path().join("a/kona.mp3"), "").unwrap();
+        fs::write(dir.path().join("b/kona.mp3"), "").unwrap();
+
+        let mut media = Media::new(Some(dir.path()), None);
+        assert_eq!(Ok(Some("kona.mp3".to_string())), media.find(Some("a/kona.mp3"), "kona", &[]));
+        assert!(media.files().is_ok());
+        assert_eq!(Ok(Some("kona.mp3".to_string())), media.find(Some("b/kona.mp3"), "kona", &[]));
+        assert!(matches!(media.files(), Err(ProgramError::Media(_))));
+    }
+}
@@ -0,0 +1,129 @@
+//! User overrides of the card templates and CSS of note types, read from a
+//! template directory.
+
+use crate::ProgramError;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Fields that Anki fills in itself, which may be used in any template.
+const SPECIAL_FIELDS: [&str; 8] =
+    ["FrontSide", "Tags", "Type", "Deck", "Subdeck", "Card", "CardFlag", "CardID"];
+
+/// A directory of template overrides. Each note type may have a
+/// subdirectory named after it, such as "Icelandic Noun", holding any of
+/// `front.html`, `back.html`, `reverse-front.html`, `reverse-back.html`,
+/// `picture-front.html`, `picture-back.html`, and `style.css`. A
+/// `style.css` at the top applies to every note type without one of its
+/// own.
+#[derive(Debug)]
+pub struct Overrides {
+    dir: PathBuf,
+}
+
+impl Overrides {
+    pub fn new(dir: &Path) -> Self {
+        Overrides { dir: dir.to_path_buf() }
+    }
+
+    /// The override of one of a note type's templates, if there is one.
+    /// Fields the template refers to must be among `fields`.
+    pub fn template(
+        &self,
+        model: &str,
+        file: &str,
+        fields: &[&str],
+    ) -> Result<Option<String>, ProgramError> {
+        let path = self.dir.join(model).join(file);
+        let template = match read_optional(&path)? {
+            Some(template) => template,
+            None => return Ok(None),
+        };
+
+        let unknown: Vec<String> = field_references(&template)
+            .into_iter()
+            .filter(|field| !fields.contains(&field.as_str()))
+            .filter(|field| !SPECIAL_FIELDS.contains(&field.as_str()))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(ProgramError::Template(format!(
+                "{} refers to fields that {} doesn't have: {}",
+                path.display(),
+                model,
+                unknown.join(", ")
+            )));
+        }
+
+        Ok(Some(template))
+    }
+
+    /// The override of a note type's CSS, if there is one.
+    pub fn css(&self, model: &str) -> Result<Option<String>, ProgramError> {
+        match read_optional(&self.dir.join(model).join("style.css"))? {
+            Some(css) => Ok(Some(css)),
+            None => read_optional(&self.dir.join("style.css")),
+        }
+    }
+}
+
+/// The contents of a file, or None if it doesn't exist.
+fn read_optional(path: &Path) -> Result<Option<String>, ProgramError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The names of the fields a template refers to, in order of first use.
+/// Sections such as `{{#Field}}` and filters such as `{{type:Field}}` refer
+/// to the field they name.
+pub fn field_references(template: &str) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+
+    for reference in template.split("{{").skip(1).filter_map(|rest| rest.split("}}").next()) {
+        let reference = reference.trim().trim_start_matches(['#', '/', '^']);
+        let field = reference.rsplit(':').next().unwrap_or_default().trim();
+
+        if !field.is_empty() && !fields.iter().any(|f| f == field) {
+            fields.push(field.to_string());
+        }
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn finds_field_references() {
+        let template = "{{FrontSide}}\n{{#Example}}<i>{{ Example }}</i>{{/Example}}\n\
+                        {{type:Form}} {{cloze:Table}} {{^Notes}}-{{/Notes}}";
+        assert_eq!(
+            vec!["FrontSide", "Example", "Form", "Table", "Notes"],
+            field_references(template)
+        );
+    }
+
+    #[test]
+    pub fn reads_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("Icelandic Noun")).unwrap();
+        fs::write(dir.path().join("Icelandic Noun/front.html"), "<b>{{Root}}</b>").unwrap();
+        fs::write(dir.path().join("Icelandic Noun/back.html"), "{{FrontSide}} {{Rot}}").unwrap();
+        fs::write(dir.path().join("style.css"), ".card {}").unwrap();
+
+        let overrides = Overrides::new(dir.path());
+        let fields = ["Root", "Definition"];
+
+        assert_eq!(
+            Some("<b>{{Root}}</b>".to_string()),
+            overrides.template("Icelandic Noun", "front.html", &fields).unwrap()
+        );
+        assert!(overrides.template("Icelandic Noun", "back.html", &fields).is_err());
+        assert_eq!(None, overrides.template("Icelandic Verb", "front.html", &fields).unwrap());
+        assert_eq!(Some(".card {}".to_string()), overrides.css("Icelandic Verb").unwrap());
+    }
+}
@@ -0,0 +1,117 @@
+//! Text-to-speech through a locally installed engine, such as espeak-ng,
+//! for words that have no recording.
+
+use crate::ids::stable_hash;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// A text-to-speech engine, run as a command. The command template is split
+/// into arguments at whitespace, and `{text}` and `{output}` are replaced in
+/// each argument by the text to speak and the path of the audio file to
+/// write, as in "espeak-ng -v is -w {output} {text}". The file is a WAV file
+/// unless the template gives another extension, as in "{output}.mp3".
+///
+/// Audio files are cached by a hash of the command and the text, so that
+/// each is only generated once.
+#[derive(Debug)]
+pub struct Tts {
+    command: String,
+    cache: PathBuf,
+    /// Set when the engine can't be run at all, after which nothing more is
+    /// spoken.
+    unavailable: bool,
+}
+
+impl Tts {
+    pub fn new(command: &str, cache: PathBuf) -> Self {
+        Tts { command: command.to_string(), cache, unavailable: false }
+    }
+
+    /// The audio file for `text`, which is generated if it isn't cached.
+    /// Returns None once the engine has been found not to run, and an error
+    /// describing any failure to generate the file.
+    pub fn speak(&mut self, text: &str) -> Result<Option<PathBuf>, String> {
+        if self.unavailable {
+            return Ok(None);
+        }
+
+        let stem = self
+            .cache
+            .join(format!("tts-{:016x}", stable_hash(&format!("{}\0{}", self.command, text))));
+        let (output, extension) = match self.command.split("{output}.").nth(1) {
+            Some(rest) => {
+                (stem.to_string_lossy().into_owned(), rest.split_whitespace().next().unwrap_or(""))
+            }
+            None => (format!("{}.wav", stem.to_string_lossy()), "wav"),
+        };
+        let path = stem.with_extension(extension);
+
+        if path.is_file() {
+            return Ok(Some(path));
+        }
+        fs::create_dir_all(&self.cache).map_err(|e| e.to_string())?;
+
+        let args: Vec<String> = self
+            .command
+            .split_whitespace()
+            .map(|arg| arg.replace("{text}", text).replace("{output}", &output))
+            .collect();
+        let (program, args) = match args.split_first() {
+            Some(split) => split,
+            None => {
+                self.unavailable = true;
+                return Err("the text-to-speech command is empty".to_string());
+            }
+        };
+
+        let status = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+
+        match status {
+            Ok(status) if status.success() && path.is_file() => Ok(Some(path)),
+            Ok(status) if status.success() => {
+                Err(format!("{} didn't write {}", program, path.display()))
+            }
+            Ok(status) => Err(format!("{} failed for \"{}\" ({})", program, text, status)),
+            Err(e) => {
+                self.unavailable = true;
+                Err(format!("cannot run {}: {}", program, e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn caches_spoken_text() {
+        let cache = tempfile::tempdir().unwrap();
+        let mut tts = Tts::new("touch {output}", cache.path().to_path_buf());
+
+        let path = tts.speak("hestur").unwrap().unwrap();
+        assert!(path.is_file());
+        assert_eq!(Some("wav"), path.extension().and_then(|e| e.to_str()));
+        assert_eq!(Some(path.clone()), tts.speak("hestur").unwrap());
+        assert_ne!(Some(path), tts.speak("kona").unwrap());
+
+        let mut mp3 = Tts::new("touch {output}.mp3", cache.path().to_path_buf());
+        let path = mp3.speak("hestur").unwrap().unwrap();
+        assert_eq!(Some("mp3"), path.extension().and_then(|e| e.to_str()));
+    }
+
+    #[test]
+    pub fn gives_up_without_engine() {
+        let cache = tempfile::tempdir().unwrap();
+        let mut tts = Tts::new("no-such-tts-engine {output} {text}", cache.path().to_path_buf());
+
+        assert!(tts.speak("hestur").is_err());
+        assert_eq!(Ok(None), tts.speak("kona"));
+    }
+}